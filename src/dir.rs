@@ -54,23 +54,29 @@ pub struct Dir(ptr::NonNull<libc::DIR>);
 
 impl Dir {
     /// Opens the given path as with `fcntl::open`.
+    ///
+    /// `O_DIRECTORY` is added to `oflag`, so this fails with `ENOTDIR` if
+    /// `path` is not a directory.
     pub fn open<P: ?Sized + NixPath>(
         path: &P,
         oflag: OFlag,
         mode: sys::stat::Mode,
     ) -> Result<Self> {
-        let fd = fcntl::open(path, oflag, mode)?;
+        let fd = fcntl::open(path, oflag | OFlag::O_DIRECTORY, mode)?;
         Dir::from_fd(fd)
     }
 
     /// Opens the given path as with `fcntl::openat`.
+    ///
+    /// `O_DIRECTORY` is added to `oflag`, so this fails with `ENOTDIR` if
+    /// `path` is not a directory.
     pub fn openat<Fd: std::os::fd::AsFd, P: ?Sized + NixPath>(
         dirfd: Fd,
         path: &P,
         oflag: OFlag,
         mode: sys::stat::Mode,
     ) -> Result<Self> {
-        let fd = fcntl::openat(dirfd, path, oflag, mode)?;
+        let fd = fcntl::openat(dirfd, path, oflag | OFlag::O_DIRECTORY, mode)?;
         Dir::from_fd(fd)
     }
 
@@ -97,6 +103,8 @@ impl Dir {
 
     /// Converts from a file descriptor, closing it on failure.
     ///
+    /// Fails with `ENOTDIR` if `fd` does not refer to a directory.
+    ///
     /// # Examples
     ///
     /// `ENOTDIR` would be returned if `fd` does not refer to a directory:
@@ -111,6 +119,12 @@ impl Dir {
     /// ```
     #[doc(alias("fdopendir"))]
     pub fn from_fd(fd: std::os::fd::OwnedFd) -> Result<Self> {
+        // Not every `fdopendir` implementation checks this itself
+        let stat = sys::stat::fstat(&fd)?;
+        if stat.st_mode & libc::S_IFMT != libc::S_IFDIR {
+            return Err(Errno::ENOTDIR);
+        }
+
         // take the ownership as the constructed `Dir` is now the owner
         let raw_fd = fd.into_raw_fd();
         let d = ptr::NonNull::new(unsafe { libc::fdopendir(raw_fd) })
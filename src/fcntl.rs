@@ -290,6 +290,38 @@ pub fn openat<P: ?Sized + NixPath, Fd: std::os::fd::AsFd>(
     Ok( unsafe { OwnedFd::from_raw_fd(fd)  } )
 }
 
+/// Open the subdirectory `name` of the directory `dirfd` as a new
+/// [`Dir`](crate::dir::Dir).
+///
+/// The returned `Dir` can itself be passed as `dirfd`, so a directory tree can
+/// be walked, e.g. to remove it recursively, using only relative paths.  That
+/// way, a concurrent rename or symlink swap of a parent directory can't send
+/// the walk outside of the tree, as it could when reopening absolute paths.
+///
+/// The directory is opened with `O_RDONLY | O_DIRECTORY | O_NOFOLLOW |
+/// O_CLOEXEC`.  If `name` is a symbolic link, this fails, with `ELOOP` on most
+/// systems; if it's not a directory, it fails with `ENOTDIR`.
+///
+/// # See Also
+/// [`Dir::openat`](crate::dir::Dir::openat)
+#[cfg(not(target_os = "redox"))]
+#[cfg(feature = "dir")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dir")))]
+pub fn openat_dir<P: ?Sized + NixPath, Fd: std::os::fd::AsFd>(
+    dirfd: Fd,
+    name: &P,
+) -> Result<crate::dir::Dir> {
+    crate::dir::Dir::openat(
+        dirfd,
+        name,
+        OFlag::O_RDONLY
+            | OFlag::O_DIRECTORY
+            | OFlag::O_NOFOLLOW
+            | OFlag::O_CLOEXEC,
+        Mode::empty(),
+    )
+}
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
         libc_bitflags! {
@@ -750,6 +782,15 @@ pub enum FcntlArg<'a> {
     /// Get the first lock that blocks the lock description
     F_GETLK(&'a mut libc::flock),
     /// Acquire or release an open file description lock
+    ///
+    /// Unlike [`F_SETLK`](FcntlArg::F_SETLK), the lock is owned by the open
+    /// file description rather than by the process.  So it is not released
+    /// when some other descriptor for the same file is closed, and two
+    /// descriptors obtained from separate `open` calls contend with each
+    /// other even within one process.  That makes these locks the right choice
+    /// for multithreaded programs.
+    ///
+    /// The `l_pid` field of the `flock` structure must be zero.
     #[cfg(linux_android)]
     F_OFD_SETLK(&'a libc::flock),
     /// Like [`F_OFD_SETLK`](FcntlArg::F_OFD_SETLK) except that if a conflicting lock is held on
@@ -778,10 +819,10 @@ pub enum FcntlArg<'a> {
     /// fsync + issue barrier to drive
     #[cfg(apple_targets)]
     F_BARRIERFSYNC,
-    /// Return the capacity of a pipe
+    /// Return the capacity of a pipe.  See [`get_pipe_size`].
     #[cfg(linux_android)]
     F_GETPIPE_SZ,
-    /// Change the capacity of a pipe
+    /// Change the capacity of a pipe.  See [`set_pipe_size`].
     #[cfg(linux_android)]
     F_SETPIPE_SZ(c_int),
     /// Look up the path of an open file descriptor, if possible.
@@ -977,7 +1018,37 @@ pub fn fcntl<Fd: std::os::fd::AsFd>(fd: Fd, arg: FcntlArg) -> Result<c_int> {
     Errno::result(res)
 }
 
-/// Operations for use with [`Flock::lock`].
+/// Return the capacity of a pipe, in bytes.
+///
+/// This is [`fcntl`] with [`F_GETPIPE_SZ`].
+///
+/// # See Also
+/// * [`fcntl(2)`](https://man7.org/linux/man-pages/man2/fcntl.2.html)
+#[cfg(linux_android)]
+pub fn get_pipe_size<Fd: std::os::fd::AsFd>(fd: Fd) -> Result<usize> {
+    fcntl(fd, F_GETPIPE_SZ).map(|size| size as usize)
+}
+
+/// Change the capacity of a pipe to at least `size` bytes, and return the
+/// capacity actually set.
+///
+/// This is [`fcntl`] with [`F_SETPIPE_SZ`].  The kernel rounds `size` up to a
+/// power-of-two number of pages.  Unprivileged processes can't go beyond
+/// `/proc/sys/fs/pipe-max-size`, failing with `EPERM`.  Shrinking a pipe below
+/// the amount of data it currently holds fails with `EBUSY`.
+///
+/// # See Also
+/// * [`fcntl(2)`](https://man7.org/linux/man-pages/man2/fcntl.2.html)
+#[cfg(linux_android)]
+pub fn set_pipe_size<Fd: std::os::fd::AsFd>(
+    fd: Fd,
+    size: usize,
+) -> Result<usize> {
+    let size = c_int::try_from(size).map_err(|_| Errno::EINVAL)?;
+    fcntl(fd, F_SETPIPE_SZ(size)).map(|size| size as usize)
+}
+
+/// Operations for use with [`flock`] and [`Flock::lock`].
 #[cfg(not(any(target_os = "redox", target_os = "solaris")))]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
@@ -997,12 +1068,26 @@ pub enum FlockArg {
     UnlockNonblock,
 }
 
-#[allow(missing_docs)]
+/// Apply or remove an advisory lock on the whole of an open file.
+///
+/// Unlike the POSIX record locks taken with [`F_SETLK`](FcntlArg::F_SETLK),
+/// `flock` locks are associated with the open file description rather than
+/// with the process.  Duplicated file descriptors share the lock, while a
+/// second `open` of the same file gets an independent one that can contend
+/// with the first.
+///
+/// A nonblocking lock request that would block fails with `EWOULDBLOCK`.
+///
+/// See [`Flock`] for a guard that releases the lock when dropped.
+///
+/// # See Also
+/// * [`flock(2)`](https://man7.org/linux/man-pages/man2/flock.2.html)
 #[cfg(not(any(target_os = "redox", target_os = "solaris")))]
-#[deprecated(since = "0.28.0", note = "`fcntl::Flock` should be used instead.")]
-pub fn flock(fd: RawFd, arg: FlockArg) -> Result<()> {
+pub fn flock<Fd: std::os::fd::AsFd>(fd: Fd, arg: FlockArg) -> Result<()> {
     use self::FlockArg::*;
+    use std::os::fd::AsRawFd;
 
+    let fd = fd.as_fd().as_raw_fd();
     let res = unsafe {
         match arg {
             LockShared => libc::flock(fd, libc::LOCK_SH),
@@ -1182,7 +1267,8 @@ libc_bitflags! {
         SPLICE_F_MORE;
         /// Gift the user pages to the kernel.
         ///
-        /// Not applicable to `splice`.
+        /// Not applicable to `splice`.  See [`vmsplice_gift`] for a safe way
+        /// to use it.
         SPLICE_F_GIFT;
     }
 }
@@ -1286,6 +1372,43 @@ pub fn splice<Fd1: std::os::fd::AsFd, Fd2: std::os::fd::AsFd>(
     Errno::result(ret).map(|r| r as usize)
 }
 
+/// Splice up to `len` bytes between two file descriptors, retrying short
+/// transfers.
+///
+/// [`splice`] may move fewer bytes than requested.  This function calls it
+/// repeatedly until `len` bytes have been moved, the input reaches end of file
+/// (a zero-length splice), or a nonblocking descriptor would block.  It
+/// returns the total number of bytes moved.  At least one of `fd_in` and
+/// `fd_out` must refer to a pipe.
+///
+/// `EAGAIN` is only returned if no bytes could be moved at all.  Interrupted
+/// calls are restarted.
+#[cfg(linux_android)]
+pub fn splice_all<Fd1: std::os::fd::AsFd, Fd2: std::os::fd::AsFd>(
+    fd_in: Fd1,
+    fd_out: Fd2,
+    len: usize,
+) -> Result<usize> {
+    let mut total = 0;
+    while total < len {
+        match splice(
+            fd_in.as_fd(),
+            None,
+            fd_out.as_fd(),
+            None,
+            len - total,
+            SpliceFFlags::empty(),
+        ) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(Errno::EINTR) => continue,
+            Err(Errno::EAGAIN) if total > 0 => break,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total)
+}
+
 /// Duplicate pipe content
 ///
 /// # See Also
@@ -1325,6 +1448,26 @@ pub fn vmsplice<F: std::os::fd::AsFd>(
     };
     Errno::result(ret).map(|r| r as usize)
 }
+
+/// Gift user pages to a pipe with `vmsplice` and `SPLICE_F_GIFT`
+///
+/// Once gifted, the pages belong to the kernel, which may hand them on to a
+/// reader of the pipe without copying.  The caller must never modify them
+/// again, so only immutable `'static` data is accepted.  For any other
+/// buffer use [`vmsplice`] without `SPLICE_F_GIFT`.
+///
+/// The kernel only avoids the copy for whole, page-aligned pages; other data
+/// is copied into the pipe as usual.
+///
+/// # See Also
+/// *[`vmsplice`](https://man7.org/linux/man-pages/man2/vmsplice.2.html)
+#[cfg(linux_android)]
+pub fn vmsplice_gift<F: std::os::fd::AsFd>(
+    fd: F,
+    data: &'static [u8],
+) -> Result<usize> {
+    vmsplice(fd, &[std::io::IoSlice::new(data)], SpliceFFlags::SPLICE_F_GIFT)
+}
 }
 
 #[cfg(target_os = "linux")]
@@ -29,6 +29,7 @@
 //! * `pthread` - POSIX threads
 //! * `ptrace` - Process tracing and debugging
 //! * `quota` - File system quotas
+//! * `random` - Obtain random bytes from the operating system
 //! * `reboot` - Reboot the system
 //! * `resource` - Process resource limits
 //! * `sched` - Manipulate process's scheduling
@@ -75,6 +76,7 @@
         feature = "pthread",
         feature = "ptrace",
         feature = "quota",
+        feature = "random",
         feature = "reboot",
         feature = "resource",
         feature = "sched",
@@ -135,6 +137,9 @@ feature! {
     #[cfg(not(target_os = "redox"))]
     #[deny(missing_docs)]
     pub mod net;
+    #[cfg(any(linux_android, bsd))]
+    #[deny(missing_docs)]
+    pub mod netdb;
 }
 #[cfg(linux_android)]
 feature! {
@@ -1,8 +1,16 @@
 use crate::{Errno, NixPath, Result};
 use libc::c_int;
+use std::ffi::{CStr, OsStr};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsFd, AsRawFd};
+use std::path::{Path, PathBuf};
+use std::{fmt, io, mem, ptr};
 
 libc_bitflags!(
     /// Used with [`mount()`] and [`unmount()`].
+    ///
+    /// `MNT_WAIT` and `MNT_NOWAIT` are not flags, and overlap with them; they
+    /// are in [`MntWaitMode`] instead.
     pub struct MntFlags: c_int {
         /// Do not interpret special files on the filesystem.
         MNT_NODEV;
@@ -24,6 +32,8 @@ libc_bitflags!(
         MNT_AUTOMOUNTED;
         /// filesystem is journaled
         MNT_JOURNALED;
+        /// The file system supports volfs, i.e. lookups by file id.
+        MNT_DOVOLFS;
         /// Don't allow user extended attributes
         MNT_NOUSERXATTR;
         /// filesystem should defer writes
@@ -62,6 +72,70 @@ libc_bitflags!(
     }
 );
 
+/// The argument of [`mount`] or [`fmount`] that a [`MountError`] refers to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MountArg {
+    /// The `source` argument.
+    Source,
+    /// The `target` argument.
+    Target,
+    /// The `data` argument.
+    Data,
+}
+
+/// The Error type of [`mount`] and [`fmount`].
+///
+/// It tells apart an argument that could not be converted to a C string from
+/// a failure of `mount(2)` itself.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MountError {
+    /// The given argument could not be converted to a C string, e.g. because
+    /// it contains an interior NUL byte or is too long.  The system call was
+    /// not made.
+    InvalidPath(MountArg, Errno),
+    /// `mount(2)` failed.
+    Mount(Errno),
+}
+
+impl MountError {
+    /// Returns the inner [`Errno`]
+    pub const fn errno(&self) -> Errno {
+        match *self {
+            MountError::InvalidPath(_, errno) | MountError::Mount(errno) => {
+                errno
+            }
+        }
+    }
+}
+
+impl std::error::Error for MountError {}
+
+impl fmt::Display for MountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MountError::InvalidPath(arg, errno) => {
+                write!(f, "invalid {:?} path: {}", arg, errno)
+            }
+            MountError::Mount(errno) => errno.fmt(f),
+        }
+    }
+}
+
+impl From<MountError> for Errno {
+    fn from(err: MountError) -> Self {
+        err.errno()
+    }
+}
+
+impl From<MountError> for io::Error {
+    fn from(err: MountError) -> Self {
+        err.errno().into()
+    }
+}
+
+/// Result type of [`mount`] and [`fmount`].
+pub type MountResult = std::result::Result<(), MountError>;
+
 /// Mount a file system.
 ///
 /// # Arguments
@@ -81,21 +155,57 @@ pub fn mount<
     target: &P2,
     flags: MntFlags,
     data: Option<&P3>,
-) -> Result<()> {
-    let res = source.with_nix_path(|s| {
-        target.with_nix_path(|t| {
+) -> MountResult {
+    let res = source
+        .with_nix_path(|s| {
+            target
+                .with_nix_path(|t| {
+                    crate::with_opt_nix_path(data, |d| unsafe {
+                        libc::mount(
+                            s.as_ptr(),
+                            t.as_ptr(),
+                            flags.bits(),
+                            d.cast_mut().cast(),
+                        )
+                    })
+                    .map_err(|e| MountError::InvalidPath(MountArg::Data, e))
+                })
+                .map_err(|e| MountError::InvalidPath(MountArg::Target, e))?
+        })
+        .map_err(|e| MountError::InvalidPath(MountArg::Source, e))??;
+
+    Errno::result(res).map(drop).map_err(MountError::Mount)
+}
+
+/// Mount a file system on the directory open as `target`.
+///
+/// This is [`mount`], with the target directory given as a file descriptor
+/// rather than a path, so that it can't be swapped out between opening and
+/// mounting it.  Available since macOS 10.13.
+///
+/// # see also
+/// [`fmount`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/mount.2.html)
+pub fn fmount<Fd: AsFd, P1: ?Sized + NixPath, P2: ?Sized + NixPath>(
+    source: &P1,
+    target: Fd,
+    flags: MntFlags,
+    data: Option<&P2>,
+) -> MountResult {
+    let res = source
+        .with_nix_path(|s| {
             crate::with_opt_nix_path(data, |d| unsafe {
-                libc::mount(
+                libc::fmount(
                     s.as_ptr(),
-                    t.as_ptr(),
+                    target.as_fd().as_raw_fd(),
                     flags.bits(),
                     d.cast_mut().cast(),
                 )
             })
+            .map_err(|e| MountError::InvalidPath(MountArg::Data, e))
         })
-    })???;
+        .map_err(|e| MountError::InvalidPath(MountArg::Source, e))??;
 
-    Errno::result(res).map(drop)
+    Errno::result(res).map(drop).map_err(MountError::Mount)
 }
 
 /// Umount the file system mounted at `target`.
@@ -109,3 +219,90 @@ where
 
     Errno::result(res).map(drop)
 }
+
+libc_enum! {
+    /// Whether [`getmntinfo`] should refresh the statistics of each file
+    /// system.
+    #[repr(i32)]
+    #[non_exhaustive]
+    pub enum MntWaitMode {
+        /// Ask each file system for up-to-date information, which may block.
+        MNT_WAIT,
+        /// Return the information cached by the kernel, without blocking.
+        MNT_NOWAIT,
+    }
+}
+
+/// A mounted file system, as returned by [`getmntinfo`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MountInfo {
+    fstype: String,
+    from: PathBuf,
+    on: PathBuf,
+    flags: MntFlags,
+}
+
+impl MountInfo {
+    /// The name of the file system type, e.g. `apfs`.
+    pub fn fstype(&self) -> &str {
+        &self.fstype
+    }
+
+    /// The mounted file system, e.g. `/dev/disk1s1`.
+    pub fn mounted_from(&self) -> &Path {
+        &self.from
+    }
+
+    /// The directory the file system is mounted on.
+    pub fn mounted_on(&self) -> &Path {
+        &self.on
+    }
+
+    /// The flags the file system is currently mounted with.
+    pub fn flags(&self) -> MntFlags {
+        self.flags
+    }
+
+    fn from_statfs(sfs: &libc::statfs) -> Self {
+        let path = |name: &[libc::c_char]| {
+            let cstr = unsafe { CStr::from_ptr(name.as_ptr()) };
+            PathBuf::from(OsStr::from_bytes(cstr.to_bytes()))
+        };
+        let fstype = unsafe { CStr::from_ptr(sfs.f_fstypename.as_ptr()) };
+        MountInfo {
+            fstype: fstype.to_string_lossy().into_owned(),
+            from: path(&sfs.f_mntfromname),
+            on: path(&sfs.f_mntonname),
+            flags: MntFlags::from_bits_truncate(sfs.f_flags as c_int),
+        }
+    }
+}
+
+/// List the currently mounted file systems.
+///
+/// Unlike `getmntinfo(3)`, which returns a buffer that libc shares between
+/// all callers and threads, this calls `getfsstat(2)` with a buffer of its
+/// own, so it is thread-safe.
+///
+/// # See Also
+/// * [`getmntinfo`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/getmntinfo.3.html)
+/// * [`getfsstat`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/getfsstat.2.html)
+pub fn getmntinfo(mode: MntWaitMode) -> Result<Vec<MountInfo>> {
+    loop {
+        let n = unsafe { libc::getfsstat(ptr::null_mut(), 0, mode as c_int) };
+        let n = Errno::result(n)? as usize;
+        // Leave room for a file system mounted in the meantime
+        let mut buf = Vec::<libc::statfs>::with_capacity(n + 1);
+        let size = mem::size_of::<libc::statfs>() * buf.capacity();
+        let size = c_int::try_from(size).map_err(|_| Errno::EOVERFLOW)?;
+        let res =
+            unsafe { libc::getfsstat(buf.as_mut_ptr(), size, mode as c_int) };
+        let res = Errno::result(res)? as usize;
+        // A full buffer may have been too small: try again
+        if res < buf.capacity() {
+            // Safe because getfsstat filled in `res` entries
+            unsafe { buf.set_len(res) };
+            return Ok(buf.iter().map(MountInfo::from_statfs).collect());
+        }
+    }
+}
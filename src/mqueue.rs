@@ -140,10 +140,72 @@ impl MqAttr {
     pub const fn curmsgs(&self) -> mq_attr_member_t {
         self.mq_attr.mq_curmsgs
     }
+
+    /// Set the maximum number of messages on the queue.
+    ///
+    /// Exceeding the system limit (`/proc/sys/fs/mqueue/msg_max` on Linux)
+    /// makes [`mq_open`] fail with `EINVAL`, unless the caller is privileged.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use nix::mqueue::*;
+    /// # use nix::sys::stat::Mode;
+    /// let attr = MqAttr::default()
+    ///     .max_messages(10)
+    ///     .message_size(64)
+    ///     .nonblocking(true);
+    /// let oflag = MQ_OFlag::O_CREAT | MQ_OFlag::O_RDWR;
+    /// let mqd = mq_open("/a_nix_queue", oflag, Mode::S_IRWXU, Some(&attr))
+    ///     .unwrap();
+    /// ```
+    pub fn max_messages(mut self, n: mq_attr_member_t) -> Self {
+        self.mq_attr.mq_maxmsg = n;
+        self
+    }
+
+    /// Set the maximum size of each message, in bytes.
+    ///
+    /// Exceeding the system limit (`/proc/sys/fs/mqueue/msgsize_max` on Linux)
+    /// makes [`mq_open`] fail with `EINVAL`, unless the caller is privileged.
+    pub fn message_size(mut self, n: mq_attr_member_t) -> Self {
+        self.mq_attr.mq_msgsize = n;
+        self
+    }
+
+    /// Set or clear `O_NONBLOCK` in the flags.
+    ///
+    /// [`mq_open`] adds `O_NONBLOCK` to its `oflag` when it is set here.
+    pub fn nonblocking(mut self, nonblocking: bool) -> Self {
+        self.mq_attr.mq_flags = if nonblocking {
+            MQ_OFlag::O_NONBLOCK.bits() as mq_attr_member_t
+        } else {
+            0
+        };
+        self
+    }
+}
+
+// Default isn't derivable because libc::mq_attr has private padding fields on
+// some platforms.
+impl Default for MqAttr {
+    /// Create an attribute with every field zeroed.
+    ///
+    /// Both [`max_messages`](MqAttr::max_messages) and
+    /// [`message_size`](MqAttr::message_size) must be set before passing it to
+    /// [`mq_open`].
+    fn default() -> Self {
+        MqAttr {
+            mq_attr: unsafe { mem::zeroed() },
+        }
+    }
 }
 
 /// Open a message queue
 ///
+/// If `attr` is given and `oflag` contains `O_CREAT`, it sets the maximum
+/// number of messages and message size of a new queue.  If `attr` has
+/// `O_NONBLOCK` in its flags, the queue is opened in non-blocking mode.
+///
 /// See also [`mq_open(2)`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/mq_open.html)
 // The mode.bits() cast is only lossless on some OSes
 #[allow(clippy::cast_lossless)]
@@ -156,6 +218,14 @@ pub fn mq_open<P>(
 where
     P: ?Sized + NixPath,
 {
+    let nonblock = attr.map_or(false, |a| {
+        a.flags() & MQ_OFlag::O_NONBLOCK.bits() as mq_attr_member_t != 0
+    });
+    let oflag = if nonblock {
+        oflag | MQ_OFlag::O_NONBLOCK
+    } else {
+        oflag
+    };
     let res = name.with_nix_path(|cstr| match attr {
         Some(mq_attr) => unsafe {
             libc::mq_open(
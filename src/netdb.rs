@@ -0,0 +1,789 @@
+//! Network database operations
+//!
+//! Translation between host and service names and socket addresses, using
+//! `getaddrinfo(3)` and `getnameinfo(3)`.
+
+use std::ffi::{CStr, CString};
+use std::os::unix::io::{AsRawFd, OwnedFd};
+use std::{fmt, mem, ptr};
+
+use crate::errno::Errno;
+use crate::sys::socket::{
+    getsockopt, socket, sockopt, AddressFamily, SockFlag, SockProtocol,
+    SockType, SockaddrLike, SockaddrStorage,
+};
+use libc::c_int;
+
+libc_bitflags! {
+    /// Flags for the `ai_flags` field of [`AddrInfo`] hints.
+    pub struct AiFlags: c_int {
+        /// The returned addresses are intended for `bind(2)` rather than
+        /// `connect(2)`.  Only meaningful when no node is given.
+        AI_PASSIVE;
+        /// Return the canonical name of the host in the first result.
+        AI_CANONNAME;
+        /// The node must be a numeric network address; no lookup is done.
+        AI_NUMERICHOST;
+        /// The service must be a numeric port number; no lookup is done.
+        AI_NUMERICSERV;
+        /// Return IPv4-mapped IPv6 addresses if no IPv6 addresses are found.
+        #[cfg(any(linux_android, apple_targets, freebsdlike))]
+        AI_V4MAPPED;
+        /// With `AI_V4MAPPED`, return both IPv6 and IPv4-mapped addresses.
+        #[cfg(any(linux_android, apple_targets, freebsdlike))]
+        AI_ALL;
+        /// Only return addresses of a family that is configured on one of
+        /// the local interfaces.
+        AI_ADDRCONFIG;
+    }
+}
+
+libc_bitflags! {
+    /// Flags for [`getnameinfo`].
+    pub struct NiFlags: c_int {
+        /// Only return the host name part of the fully qualified domain name
+        /// for local hosts.
+        NI_NOFQDN;
+        /// Return the numeric form of the host address instead of its name.
+        NI_NUMERICHOST;
+        /// Fail with `EAI_NONAME` if the host name can't be determined,
+        /// instead of returning the numeric address.
+        NI_NAMEREQD;
+        /// Return the numeric form of the port instead of the service name.
+        NI_NUMERICSERV;
+        /// Look up the service as a datagram (UDP) service rather than a
+        /// stream (TCP) one.
+        NI_DGRAM;
+    }
+}
+
+/// Error returned by [`getaddrinfo`] and [`getnameinfo`].
+///
+/// Name resolution functions don't report their errors through `errno`, but
+/// with their own set of `EAI_*` codes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AddressInfoError {
+    /// The name server returned a temporary failure.
+    EAI_AGAIN,
+    /// Invalid value in the hints' `ai_flags`.
+    EAI_BADFLAGS,
+    /// The name server returned a permanent failure.
+    EAI_FAIL,
+    /// The requested address family is not supported.
+    EAI_FAMILY,
+    /// Out of memory.
+    EAI_MEMORY,
+    /// The host exists, but has no addresses.
+    #[cfg(any(linux_android, apple_targets, netbsdlike))]
+    EAI_NODATA,
+    /// The node or service is not known.
+    EAI_NONAME,
+    /// The service is not available for the requested socket type.
+    EAI_SERVICE,
+    /// The requested socket type is not supported.
+    EAI_SOCKTYPE,
+    /// A system error occurred; the inner value holds `errno`.
+    EAI_SYSTEM(Errno),
+    /// An argument buffer overflowed.
+    EAI_OVERFLOW,
+    /// An error code not known to nix.
+    Unknown(c_int),
+    /// Resolution succeeded, but returned no address to connect to.
+    NoAddress,
+}
+
+impl AddressInfoError {
+    /// Convert a return value of `getaddrinfo` or `getnameinfo` into an
+    /// `AddressInfoError`.
+    ///
+    /// Must be called immediately after the failing call, so that `errno` is
+    /// still valid in the `EAI_SYSTEM` case.
+    fn from_code(code: c_int) -> Self {
+        match code {
+            libc::EAI_AGAIN => AddressInfoError::EAI_AGAIN,
+            libc::EAI_BADFLAGS => AddressInfoError::EAI_BADFLAGS,
+            libc::EAI_FAIL => AddressInfoError::EAI_FAIL,
+            libc::EAI_FAMILY => AddressInfoError::EAI_FAMILY,
+            libc::EAI_MEMORY => AddressInfoError::EAI_MEMORY,
+            #[cfg(any(linux_android, apple_targets, netbsdlike))]
+            libc::EAI_NODATA => AddressInfoError::EAI_NODATA,
+            libc::EAI_NONAME => AddressInfoError::EAI_NONAME,
+            libc::EAI_SERVICE => AddressInfoError::EAI_SERVICE,
+            libc::EAI_SOCKTYPE => AddressInfoError::EAI_SOCKTYPE,
+            libc::EAI_SYSTEM => AddressInfoError::EAI_SYSTEM(Errno::last()),
+            libc::EAI_OVERFLOW => AddressInfoError::EAI_OVERFLOW,
+            other => AddressInfoError::Unknown(other),
+        }
+    }
+
+    /// The `EAI_*` code this error was created from, if any.
+    fn code(&self) -> Option<c_int> {
+        Some(match *self {
+            AddressInfoError::EAI_AGAIN => libc::EAI_AGAIN,
+            AddressInfoError::EAI_BADFLAGS => libc::EAI_BADFLAGS,
+            AddressInfoError::EAI_FAIL => libc::EAI_FAIL,
+            AddressInfoError::EAI_FAMILY => libc::EAI_FAMILY,
+            AddressInfoError::EAI_MEMORY => libc::EAI_MEMORY,
+            #[cfg(any(linux_android, apple_targets, netbsdlike))]
+            AddressInfoError::EAI_NODATA => libc::EAI_NODATA,
+            AddressInfoError::EAI_NONAME => libc::EAI_NONAME,
+            AddressInfoError::EAI_SERVICE => libc::EAI_SERVICE,
+            AddressInfoError::EAI_SOCKTYPE => libc::EAI_SOCKTYPE,
+            AddressInfoError::EAI_SYSTEM(_) => libc::EAI_SYSTEM,
+            AddressInfoError::EAI_OVERFLOW => libc::EAI_OVERFLOW,
+            AddressInfoError::Unknown(code) => code,
+            AddressInfoError::NoAddress => return None,
+        })
+    }
+}
+
+impl fmt::Display for AddressInfoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = match *self {
+            AddressInfoError::EAI_SYSTEM(errno) => {
+                return f.write_str(errno.desc())
+            }
+            _ => match self.code() {
+                Some(code) => code,
+                None => return f.write_str("No address to connect to"),
+            },
+        };
+        // gai_strerror returns a pointer to a static, NUL-terminated string,
+        // which stays valid for the whole program.
+        let msg = unsafe { CStr::from_ptr(libc::gai_strerror(code)) };
+        f.write_str(&msg.to_string_lossy())
+    }
+}
+
+impl std::error::Error for AddressInfoError {}
+
+/// A single entry of the list returned by [`getaddrinfo`], or a set of
+/// hints to pass to it.
+///
+/// Use [`AddrInfo::default`] to create an empty set of hints.
+#[derive(Debug)]
+// Entries point into the list they belong to, so copying one out would let
+// it outlive the list.
+#[allow(missing_copy_implementations)]
+#[repr(transparent)]
+pub struct AddrInfo(libc::addrinfo);
+
+impl Default for AddrInfo {
+    fn default() -> Self {
+        // All-zero is a valid addrinfo with null pointers: no flags, any
+        // family, any socket type and any protocol.
+        AddrInfo(unsafe { mem::zeroed() })
+    }
+}
+
+impl AddrInfo {
+    /// Flags of this entry; for hints, which extra options to apply.
+    pub fn flags(&self) -> AiFlags {
+        AiFlags::from_bits_truncate(self.0.ai_flags)
+    }
+
+    /// Set the `ai_flags` field.
+    pub fn set_flags(&mut self, flags: AiFlags) {
+        self.0.ai_flags = flags.bits();
+    }
+
+    /// Address family of this entry, or `None` if it isn't one known to nix.
+    /// For hints, `None` means any family.
+    pub fn family(&self) -> Option<AddressFamily> {
+        AddressFamily::from_i32(self.0.ai_family)
+    }
+
+    /// Set the `ai_family` field.
+    pub fn set_family(&mut self, family: AddressFamily) {
+        self.0.ai_family = family as c_int;
+    }
+
+    /// Socket type of this entry, or `None` if it is any or unknown type.
+    pub fn socktype(&self) -> Option<SockType> {
+        SockType::try_from(self.0.ai_socktype).ok()
+    }
+
+    /// Set the `ai_socktype` field.
+    pub fn set_socktype(&mut self, socktype: SockType) {
+        self.0.ai_socktype = socktype as c_int;
+    }
+
+    /// Protocol of this entry, or `None` if it is any or unknown protocol.
+    pub fn protocol(&self) -> Option<SockProtocol> {
+        match self.0.ai_protocol {
+            libc::IPPROTO_TCP => Some(SockProtocol::Tcp),
+            libc::IPPROTO_UDP => Some(SockProtocol::Udp),
+            libc::IPPROTO_RAW => Some(SockProtocol::Raw),
+            libc::IPPROTO_ICMP => Some(SockProtocol::Icmp),
+            libc::IPPROTO_ICMPV6 => Some(SockProtocol::IcmpV6),
+            #[cfg(any(
+                apple_targets,
+                linux_android,
+                target_os = "freebsd",
+                target_os = "netbsd"
+            ))]
+            libc::IPPROTO_SCTP => Some(SockProtocol::Sctp),
+            _ => None,
+        }
+    }
+
+    /// Set the `ai_protocol` field.
+    pub fn set_protocol(&mut self, protocol: SockProtocol) {
+        self.0.ai_protocol = protocol as c_int;
+    }
+
+    /// The socket address of this entry, if any.
+    ///
+    /// Only the first `ai_addrlen` bytes of `ai_addr` are copied, verbatim, so
+    /// for IPv6 the scope ID of link-local addresses and the flow information
+    /// are kept.
+    pub fn sockaddr(&self) -> Option<SockaddrStorage> {
+        if self.0.ai_addr.is_null() {
+            return None;
+        }
+        unsafe {
+            SockaddrStorage::from_raw(self.0.ai_addr, Some(self.0.ai_addrlen))
+        }
+    }
+
+    /// The canonical name of the host, if `AI_CANONNAME` was requested.
+    ///
+    /// Only the first entry of a list carries it.  Names that aren't valid
+    /// UTF-8 are returned as `None` too.
+    pub fn canonname(&self) -> Option<&str> {
+        if self.0.ai_canonname.is_null() {
+            None
+        } else {
+            unsafe { CStr::from_ptr(self.0.ai_canonname) }.to_str().ok()
+        }
+    }
+
+    /// The next entry in the list, if any.
+    pub fn next(&self) -> Option<&AddrInfo> {
+        unsafe { self.0.ai_next.cast::<AddrInfo>().as_ref() }
+    }
+
+    /// Iterate over this entry and the ones following it in the list.
+    pub fn iter(&self) -> AddrInfoIter<'_> {
+        AddrInfoIter { next: Some(self) }
+    }
+
+    /// Copy this entry into an [`OwnedAddrInfo`], which does not borrow from
+    /// the list.
+    pub fn into_owned(&self) -> OwnedAddrInfo {
+        OwnedAddrInfo {
+            family: self.family(),
+            socktype: self.socktype(),
+            protocol: self.protocol(),
+            sockaddr: self.sockaddr(),
+            canonname: self.canonname().map(str::to_owned),
+        }
+    }
+}
+
+/// An entry of an [`AddrInfoList`], copied out of it with
+/// [`AddrInfo::into_owned`].
+///
+/// Unlike [`AddrInfo`], this can outlive the list, and be sent to other
+/// threads.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAddrInfo {
+    family: Option<AddressFamily>,
+    socktype: Option<SockType>,
+    protocol: Option<SockProtocol>,
+    sockaddr: Option<SockaddrStorage>,
+    canonname: Option<String>,
+}
+
+impl OwnedAddrInfo {
+    /// Address family of this entry, or `None` if it isn't one known to nix.
+    pub fn family(&self) -> Option<AddressFamily> {
+        self.family
+    }
+
+    /// Socket type of this entry, or `None` if it is any or unknown type.
+    pub fn socktype(&self) -> Option<SockType> {
+        self.socktype
+    }
+
+    /// Protocol of this entry, or `None` if it is any or unknown protocol.
+    pub fn protocol(&self) -> Option<SockProtocol> {
+        self.protocol
+    }
+
+    /// The socket address of this entry, if any.
+    pub fn sockaddr(&self) -> Option<&SockaddrStorage> {
+        self.sockaddr.as_ref()
+    }
+
+    /// The canonical name of the host, if `AI_CANONNAME` was requested.
+    pub fn canonname(&self) -> Option<&str> {
+        self.canonname.as_deref()
+    }
+}
+
+/// Builder for the hints passed to [`getaddrinfo`].
+///
+/// Fields that aren't set don't restrict the results.
+///
+/// # Examples
+///
+/// ```
+/// # use nix::netdb::{getaddrinfo, AddrInfoHints, AiFlags};
+/// # use nix::sys::socket::{AddressFamily, SockType};
+/// let hints = AddrInfoHints::new()
+///     .family(AddressFamily::Inet)
+///     .socktype(SockType::Stream)
+///     .flags(AiFlags::AI_NUMERICHOST);
+/// let list =
+///     getaddrinfo(Some("127.0.0.1"), None, Some(hints.as_ref())).unwrap();
+/// assert!(list.iter().all(|ai| ai.socktype() == Some(SockType::Stream)));
+/// ```
+#[derive(Debug, Default)]
+pub struct AddrInfoHints(AddrInfo);
+
+impl AddrInfoHints {
+    /// Hints that don't restrict the results at all.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return addresses of this family.
+    pub fn family(mut self, family: AddressFamily) -> Self {
+        self.0.set_family(family);
+        self
+    }
+
+    /// Only return addresses for this socket type.
+    pub fn socktype(mut self, socktype: SockType) -> Self {
+        self.0.set_socktype(socktype);
+        self
+    }
+
+    /// Only return addresses for this protocol.
+    pub fn protocol(mut self, protocol: SockProtocol) -> Self {
+        self.0.set_protocol(protocol);
+        self
+    }
+
+    /// Pass these flags to `getaddrinfo`.
+    pub fn flags(mut self, flags: AiFlags) -> Self {
+        self.0.set_flags(flags);
+        self
+    }
+}
+
+impl Clone for AddrInfoHints {
+    fn clone(&self) -> Self {
+        // Hints never point anywhere, so copying them is fine.
+        AddrInfoHints(AddrInfo(self.0 .0))
+    }
+}
+
+impl AsRef<AddrInfo> for AddrInfoHints {
+    fn as_ref(&self) -> &AddrInfo {
+        &self.0
+    }
+}
+
+/// List of results of [`getaddrinfo`].
+///
+/// The list is freed with `freeaddrinfo` when this goes out of scope.
+#[derive(Debug)]
+pub struct AddrInfoList {
+    // Null if the list is empty
+    head: *mut libc::addrinfo,
+}
+
+impl AddrInfoList {
+    /// Iterate over the entries of the list.
+    pub fn iter(&self) -> AddrInfoIter<'_> {
+        AddrInfoIter {
+            next: unsafe { self.head.cast::<AddrInfo>().as_ref() },
+        }
+    }
+
+    /// Whether the list has no entries.
+    ///
+    /// `getaddrinfo` normally reports an error rather than an empty list, but
+    /// some implementations don't.
+    pub fn is_empty(&self) -> bool {
+        self.head.is_null()
+    }
+}
+
+impl Drop for AddrInfoList {
+    fn drop(&mut self) {
+        if !self.head.is_null() {
+            unsafe { libc::freeaddrinfo(self.head) };
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a AddrInfoList {
+    type Item = &'a AddrInfo;
+    type IntoIter = AddrInfoIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of an [`AddrInfoList`].
+#[derive(Clone, Debug)]
+pub struct AddrInfoIter<'a> {
+    next: Option<&'a AddrInfo>,
+}
+
+impl<'a> Iterator for AddrInfoIter<'a> {
+    type Item = &'a AddrInfo;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.next();
+        Some(current)
+    }
+}
+
+/// Convert a node or service name for `getaddrinfo`.
+fn to_cstring(s: Option<&str>) -> Result<Option<CString>, AddressInfoError> {
+    s.map(CString::new)
+        .transpose()
+        .map_err(|_| AddressInfoError::EAI_SYSTEM(Errno::EINVAL))
+}
+
+/// Translate a host and service name into a list of socket addresses.
+///
+/// At least one of `node` and `service` must be given.  `hints`, if given,
+/// restricts the results to the family, socket type and protocol it sets,
+/// and passes the [`AiFlags`] it carries.
+///
+/// # See Also
+/// [getaddrinfo(3)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/getaddrinfo.html)
+pub fn getaddrinfo(
+    node: Option<&str>,
+    service: Option<&str>,
+    hints: Option<&AddrInfo>,
+) -> Result<AddrInfoList, AddressInfoError> {
+    let node = to_cstring(node)?;
+    let service = to_cstring(service)?;
+    let mut res = ptr::null_mut();
+
+    let code = unsafe {
+        libc::getaddrinfo(
+            node.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            service.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            hints.map_or(ptr::null(), |h| &h.0),
+            &mut res,
+        )
+    };
+    if code != 0 {
+        return Err(AddressInfoError::from_code(code));
+    }
+
+    Ok(AddrInfoList { head: res })
+}
+
+/// Maximum length of a host name returned by [`getnameinfo`], from glibc.
+const NI_MAXHOST: usize = 1025;
+/// Maximum length of a service name returned by [`getnameinfo`].
+const NI_MAXSERV: usize = 32;
+
+/// Translate a socket address into a host and a service name.
+///
+/// Names that aren't valid UTF-8 are converted lossily.
+///
+/// # Example
+/// ```
+/// # use nix::netdb::{getnameinfo, NiFlags};
+/// # use nix::sys::socket::SockaddrIn;
+/// let addr = SockaddrIn::new(127, 0, 0, 1, 80);
+/// let flags = NiFlags::NI_NUMERICHOST | NiFlags::NI_NUMERICSERV;
+/// let (host, service) = getnameinfo(&addr, flags).unwrap();
+/// assert_eq!(host, "127.0.0.1");
+/// assert_eq!(service, "80");
+/// ```
+///
+/// # See Also
+/// [getnameinfo(3)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/getnameinfo.html)
+pub fn getnameinfo(
+    addr: &dyn SockaddrLike,
+    flags: NiFlags,
+) -> Result<(String, String), AddressInfoError> {
+    let mut host = [0u8; NI_MAXHOST];
+    let mut service = [0u8; NI_MAXSERV];
+
+    nameinfo(addr, Some(&mut host), Some(&mut service), flags)?;
+    Ok((buf_to_string(&host), buf_to_string(&service)))
+}
+
+/// Look up the host name of a socket address.
+///
+/// Unlike [`getnameinfo`], this fails with
+/// [`AddressInfoError::EAI_NONAME`] if the address has no name, rather than
+/// returning it in numeric form.  Names that aren't valid UTF-8 are converted
+/// lossily.
+///
+/// # Example
+/// ```no_run
+/// # use nix::netdb::reverse_lookup;
+/// # use nix::sys::socket::SockaddrIn;
+/// let addr = SockaddrIn::new(127, 0, 0, 1, 0);
+/// let host = reverse_lookup(&addr).unwrap();
+/// assert_ne!(host, "127.0.0.1");
+/// ```
+pub fn reverse_lookup(
+    addr: &dyn SockaddrLike,
+) -> Result<String, AddressInfoError> {
+    let mut host = [0u8; NI_MAXHOST];
+
+    nameinfo(addr, Some(&mut host), None, NiFlags::NI_NAMEREQD)?;
+    Ok(buf_to_string(&host))
+}
+
+/// Look up the service name of a socket address's port.
+///
+/// This is [`getnameinfo`] without the host part.  With
+/// [`NiFlags::NI_DGRAM`], the port is looked up as a UDP service.  Ports
+/// without a name are returned in numeric form.
+pub fn service_name(
+    addr: &dyn SockaddrLike,
+    flags: NiFlags,
+) -> Result<String, AddressInfoError> {
+    let mut service = [0u8; NI_MAXSERV];
+
+    nameinfo(addr, None, Some(&mut service), flags)?;
+    Ok(buf_to_string(&service))
+}
+
+/// Call `getnameinfo`, only looking up the parts that have a buffer.
+fn nameinfo(
+    addr: &dyn SockaddrLike,
+    host: Option<&mut [u8]>,
+    service: Option<&mut [u8]>,
+    flags: NiFlags,
+) -> Result<(), AddressInfoError> {
+    let (host, hostlen) =
+        host.map_or((ptr::null_mut(), 0), |b| (b.as_mut_ptr(), b.len()));
+    let (service, servicelen) =
+        service.map_or((ptr::null_mut(), 0), |b| (b.as_mut_ptr(), b.len()));
+
+    let code = unsafe {
+        libc::getnameinfo(
+            addr.as_ptr(),
+            addr.len(),
+            host.cast(),
+            hostlen as _,
+            service.cast(),
+            servicelen as _,
+            flags.bits(),
+        )
+    };
+    if code != 0 {
+        return Err(AddressInfoError::from_code(code));
+    }
+    Ok(())
+}
+
+fn buf_to_string(buf: &[u8]) -> String {
+    let s = CStr::from_bytes_until_nul(buf).unwrap_or_default();
+    s.to_string_lossy().into_owned()
+}
+
+/// Resolve `node` and `service`, and connect to the first address that
+/// accepts the connection.
+///
+/// Each address returned by [`getaddrinfo`] is tried in turn: a
+/// close-on-exec socket is created for it and connected.  The first
+/// connected socket is returned.  If resolution fails, its error is returned;
+/// if it returns no address, [`AddressInfoError::NoAddress`] is.  If every
+/// address fails, the `errno` of the last failed `socket(2)` or `connect(2)`
+/// call is returned as [`AddressInfoError::EAI_SYSTEM`].
+///
+/// # Example
+/// ```no_run
+/// # use nix::netdb::{connect_any, AddrInfo};
+/// # use nix::sys::socket::SockType;
+/// let mut hints = AddrInfo::default();
+/// hints.set_socktype(SockType::Stream);
+/// let fd = connect_any("localhost", "80", Some(&hints)).unwrap();
+/// ```
+pub fn connect_any(
+    node: &str,
+    service: &str,
+    hints: Option<&AddrInfo>,
+) -> Result<OwnedFd, AddressInfoError> {
+    let list = getaddrinfo(Some(node), Some(service), hints)?;
+    let mut last_err = None;
+
+    for ai in &list {
+        // Entries of a family or type unknown to nix can't be connected
+        let (Some(family), Some(socktype)) = (ai.family(), ai.socktype())
+        else {
+            last_err = Some(Errno::EAFNOSUPPORT);
+            continue;
+        };
+        // Where SOCK_CLOEXEC is missing, socket() sets close-on-exec itself
+        #[cfg(any(linux_android, freebsdlike, netbsdlike, solarish))]
+        let flags = SockFlag::SOCK_CLOEXEC;
+        #[cfg(not(any(linux_android, freebsdlike, netbsdlike, solarish)))]
+        let flags = SockFlag::empty();
+        let res = socket(family, socktype, flags, ai.protocol())
+            .and_then(|fd| connect_retry(&fd, &ai.0).map(|()| fd));
+        match res {
+            Ok(fd) => return Ok(fd),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err
+        .map_or(AddressInfoError::NoAddress, AddressInfoError::EAI_SYSTEM))
+}
+
+/// Connect `fd` to the address of `ai`, waiting for the connection to
+/// complete if `connect(2)` is interrupted by a signal.
+fn connect_retry(fd: &OwnedFd, ai: &libc::addrinfo) -> crate::Result<()> {
+    let res =
+        unsafe { libc::connect(fd.as_raw_fd(), ai.ai_addr, ai.ai_addrlen) };
+    match Errno::result(res) {
+        Err(Errno::EINTR) => (),
+        res => return res.map(drop),
+    }
+
+    // The connection keeps being established in the background, and calling
+    // connect(2) again would fail with EALREADY: wait for it instead.
+    let mut pfd = libc::pollfd {
+        fd: fd.as_raw_fd(),
+        events: libc::POLLOUT,
+        revents: 0,
+    };
+    loop {
+        match Errno::result(unsafe { libc::poll(&mut pfd, 1, -1) }) {
+            Err(Errno::EINTR) => continue,
+            res => {
+                res?;
+                break;
+            }
+        }
+    }
+    match getsockopt(fd, sockopt::SocketError)? {
+        0 => Ok(()),
+        errno => Err(Errno::from_raw(errno)),
+    }
+}
+
+/// An entry of the services database, as returned by [`getservbyname`] and
+/// [`getservbyport`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ServiceEntry {
+    name: String,
+    aliases: Vec<String>,
+    port: u16,
+    proto: String,
+}
+
+impl ServiceEntry {
+    /// Copy a `servent` returned by libc.
+    ///
+    /// # Safety
+    ///
+    /// All the pointers in `ent` must be valid.
+    unsafe fn from_raw(ent: &libc::servent) -> Self {
+        let to_string =
+            |p| unsafe { CStr::from_ptr(p) }.to_string_lossy().into_owned();
+        let mut aliases = Vec::new();
+        let mut alias = ent.s_aliases;
+        while !unsafe { *alias }.is_null() {
+            aliases.push(to_string(unsafe { *alias }));
+            alias = unsafe { alias.add(1) };
+        }
+
+        ServiceEntry {
+            name: to_string(ent.s_name),
+            aliases,
+            // s_port is stored in network byte order
+            port: u16::from_be(ent.s_port as u16),
+            proto: to_string(ent.s_proto),
+        }
+    }
+
+    /// Official name of the service.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Alternative names of the service.
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// Port of the service, in host byte order.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Protocol to use with the service, e.g. `"tcp"`.
+    pub fn proto(&self) -> &str {
+        &self.proto
+    }
+}
+
+/// Convert the result of `getservbyname` or `getservbyport`.
+fn getserv(ent: *const libc::servent) -> crate::Result<Option<ServiceEntry>> {
+    if ent.is_null() {
+        Ok(None)
+    } else {
+        Ok(Some(unsafe { ServiceEntry::from_raw(&*ent) }))
+    }
+}
+
+/// Look up a service by name, e.g. `"https"`.
+///
+/// If `proto` is given, only services for that protocol (e.g. `"tcp"`) are
+/// considered.  Returns `Ok(None)` if there is no such service.  Fails with
+/// `EINVAL` if `name` or `proto` contains a NUL byte.
+///
+/// This is not thread-safe: `getservbyname` returns a static buffer, which a
+/// concurrent call to it, to [`getservbyport`] or to another function of the
+/// `getservent` family from another thread may overwrite while it's copied.
+///
+/// # See Also
+/// [getservbyname(3)](https://man7.org/linux/man-pages/man3/getservbyname.3.html)
+pub fn getservbyname(
+    name: &str,
+    proto: Option<&str>,
+) -> crate::Result<Option<ServiceEntry>> {
+    let name = CString::new(name).map_err(|_| Errno::EINVAL)?;
+    let proto = proto
+        .map(CString::new)
+        .transpose()
+        .map_err(|_| Errno::EINVAL)?;
+    let proto_ptr = proto.as_ref().map_or(ptr::null(), |p| p.as_ptr());
+
+    getserv(unsafe { libc::getservbyname(name.as_ptr(), proto_ptr) })
+}
+
+/// Look up a service by port, given in host byte order.
+///
+/// If `proto` is given, only services for that protocol (e.g. `"tcp"`) are
+/// considered.  Returns `Ok(None)` if there is no such service.  Fails with
+/// `EINVAL` if `proto` contains a NUL byte.
+///
+/// This is not thread-safe, for the same reason as [`getservbyname`].
+///
+/// # See Also
+/// [getservbyport(3)](https://man7.org/linux/man-pages/man3/getservbyport.3.html)
+pub fn getservbyport(
+    port: u16,
+    proto: Option<&str>,
+) -> crate::Result<Option<ServiceEntry>> {
+    let proto = proto
+        .map(CString::new)
+        .transpose()
+        .map_err(|_| Errno::EINVAL)?;
+    let proto_ptr = proto.as_ref().map_or(ptr::null(), |p| p.as_ptr());
+    // The port is passed in network byte order
+    let port = c_int::from(port.to_be());
+
+    getserv(unsafe { libc::getservbyport(port, proto_ptr) })
+}
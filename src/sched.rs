@@ -153,6 +153,45 @@ mod sched_linux_like {
 
         Errno::result(res).map(drop)
     }
+
+    /// Determine the CPU and NUMA node on which the calling thread is running
+    ///
+    /// Returns `(cpu, node)`.  Like with
+    /// [`sched_getcpu`](crate::sched::sched_getcpu), the answer may already be
+    /// stale when this returns.
+    ///
+    /// See also [getcpu(2)](https://man7.org/linux/man-pages/man2/getcpu.2.html)
+    #[cfg(target_os = "linux")]
+    pub fn getcpu() -> Result<(u32, u32)> {
+        let mut cpu: libc::c_uint = 0;
+        let mut node: libc::c_uint = 0;
+        // The third argument is unused since Linux 2.6.24
+        let res = unsafe {
+            libc::syscall(
+                libc::SYS_getcpu,
+                &mut cpu as *mut libc::c_uint,
+                &mut node as *mut libc::c_uint,
+                std::ptr::null_mut::<c_void>(),
+            )
+        };
+
+        Errno::result(res).map(|_| (cpu, node))
+    }
+
+    /// reassociate thread with several namespaces of another process at once
+    ///
+    /// `pidfd` is a process file descriptor, as returned by
+    /// [`pidfd_open`](crate::unistd::pidfd_open), and `nstypes` selects which
+    /// of that process's namespaces to enter.  The change is atomic: either
+    /// all of them are entered, or none.  `nstypes` must contain at least one
+    /// `CLONE_NEW*` flag, otherwise `EINVAL` is returned.  Available since
+    /// Linux 5.8.
+    ///
+    /// See also [setns(2)](https://man7.org/linux/man-pages/man2/setns.2.html)
+    #[cfg(target_os = "linux")]
+    pub fn setns_pidfd<Fd: AsFd>(pidfd: Fd, nstypes: CloneFlags) -> Result<()> {
+        setns(pidfd, nstypes)
+    }
 }
 
 #[cfg(any(linux_android, freebsdlike))]
@@ -309,6 +348,11 @@ mod sched_affinity {
     }
 
     /// Determines the CPU on which the calling thread is running.
+    ///
+    /// The answer may already be stale when this returns, unless the thread's
+    /// affinity is restricted to a single CPU with [`sched_setaffinity`].
+    ///
+    /// See also [sched_getcpu(3)](https://man7.org/linux/man-pages/man3/sched_getcpu.3.html)
     pub fn sched_getcpu() -> Result<usize> {
         let res = unsafe { libc::sched_getcpu() };
 
@@ -1,6 +1,8 @@
 use crate::errno::Errno;
 use crate::{unistd, Result};
-use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::io::{
+    AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd,
+};
 
 libc_bitflags! {
     /// Eventfd flags.
@@ -32,9 +34,9 @@ pub fn eventfd(initval: libc::c_uint, flags: EfdFlags) -> Result<OwnedFd> {
 pub struct EventFd(OwnedFd);
 
 impl EventFd {
-    /// [`EventFd::from_value_and_flags`] with `init_val = 0` and `flags = EfdFlags::empty()`.
+    /// [`EventFd::from_value_and_flags`] with `init_val = 0` and `flags = EfdFlags::EFD_CLOEXEC`.
     pub fn new() -> Result<Self> {
-        Self::from_value_and_flags(0, EfdFlags::empty())
+        Self::from_value_and_flags(0, EfdFlags::EFD_CLOEXEC)
     }
 
     /// Constructs [`EventFd`] with the given `init_val` and `flags`.
@@ -53,9 +55,9 @@ impl EventFd {
         Self::from_value_and_flags(0, flags)
     }
 
-    /// [`EventFd::from_value_and_flags`] with given `init_val` and `flags = EfdFlags::empty()`.
+    /// [`EventFd::from_value_and_flags`] with given `init_val` and `flags = EfdFlags::EFD_CLOEXEC`.
     pub fn from_value(init_val: u32) -> Result<Self> {
-        Self::from_value_and_flags(init_val, EfdFlags::empty())
+        Self::from_value_and_flags(init_val, EfdFlags::EFD_CLOEXEC)
     }
 
     /// Constructs an `EventFd` wrapping an existing `OwnedFd`.
@@ -110,6 +112,16 @@ impl AsRawFd for EventFd {
         self.0.as_raw_fd()
     }
 }
+impl IntoRawFd for EventFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
+    }
+}
+impl FromRawFd for EventFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        Self(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+}
 
 impl From<EventFd> for OwnedFd {
     fn from(value: EventFd) -> Self {
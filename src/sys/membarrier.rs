@@ -0,0 +1,98 @@
+//! Issue memory barriers on a set of threads.
+//!
+//! `membarrier` lets the slow path of an asymmetric synchronization scheme
+//! force a memory barrier on other running threads, so that the fast path
+//! can make do with a compiler barrier.
+//!
+//! For more documentation, please read
+//! [membarrier(2)](https://man7.org/linux/man-pages/man2/membarrier.2.html).
+use crate::errno::Errno;
+use crate::Result;
+use libc::{c_int, c_uint};
+
+/// Commands for [`membarrier`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum MembarrierCmd {
+    /// Return the set of supported commands.
+    Query = libc::MEMBARRIER_CMD_QUERY,
+    /// Issue a memory barrier on all running threads of all processes.
+    ///
+    /// Also known as `MEMBARRIER_CMD_SHARED`.
+    Global = libc::MEMBARRIER_CMD_GLOBAL,
+    /// Issue a memory barrier on all running threads of all processes that
+    /// registered with [`RegisterGlobalExpedited`](Self::RegisterGlobalExpedited).
+    GlobalExpedited = libc::MEMBARRIER_CMD_GLOBAL_EXPEDITED,
+    /// Register the process for
+    /// [`GlobalExpedited`](Self::GlobalExpedited) barriers.
+    RegisterGlobalExpedited = libc::MEMBARRIER_CMD_REGISTER_GLOBAL_EXPEDITED,
+    /// Issue a memory barrier on all running threads of the calling process.
+    ///
+    /// The process must have registered with
+    /// [`RegisterPrivateExpedited`](Self::RegisterPrivateExpedited).
+    PrivateExpedited = libc::MEMBARRIER_CMD_PRIVATE_EXPEDITED,
+    /// Register the process for
+    /// [`PrivateExpedited`](Self::PrivateExpedited) barriers.
+    RegisterPrivateExpedited = libc::MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED,
+    /// Like [`PrivateExpedited`](Self::PrivateExpedited), but also
+    /// synchronize the instruction streams of the threads.
+    PrivateExpeditedSyncCore = libc::MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE,
+    /// Register the process for
+    /// [`PrivateExpeditedSyncCore`](Self::PrivateExpeditedSyncCore) barriers.
+    RegisterPrivateExpeditedSyncCore =
+        libc::MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_SYNC_CORE,
+    /// Restart the restartable sequences of all running threads of the
+    /// calling process.
+    PrivateExpeditedRseq = libc::MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ,
+    /// Register the process for
+    /// [`PrivateExpeditedRseq`](Self::PrivateExpeditedRseq).
+    RegisterPrivateExpeditedRseq =
+        libc::MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_RSEQ,
+}
+
+libc_bitflags! {
+    /// Set of [`MembarrierCmd`]s, as returned by [`membarrier_query`].
+    pub struct MembarrierCmdSet: c_int {
+        /// [`MembarrierCmd::Global`]
+        MEMBARRIER_CMD_GLOBAL;
+        /// [`MembarrierCmd::GlobalExpedited`]
+        MEMBARRIER_CMD_GLOBAL_EXPEDITED;
+        /// [`MembarrierCmd::RegisterGlobalExpedited`]
+        MEMBARRIER_CMD_REGISTER_GLOBAL_EXPEDITED;
+        /// [`MembarrierCmd::PrivateExpedited`]
+        MEMBARRIER_CMD_PRIVATE_EXPEDITED;
+        /// [`MembarrierCmd::RegisterPrivateExpedited`]
+        MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED;
+        /// [`MembarrierCmd::PrivateExpeditedSyncCore`]
+        MEMBARRIER_CMD_PRIVATE_EXPEDITED_SYNC_CORE;
+        /// [`MembarrierCmd::RegisterPrivateExpeditedSyncCore`]
+        MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_SYNC_CORE;
+        /// [`MembarrierCmd::PrivateExpeditedRseq`]
+        MEMBARRIER_CMD_PRIVATE_EXPEDITED_RSEQ;
+        /// [`MembarrierCmd::RegisterPrivateExpeditedRseq`]
+        MEMBARRIER_CMD_REGISTER_PRIVATE_EXPEDITED_RSEQ;
+    }
+}
+
+/// Issue a memory barrier, or register for or query barrier commands.
+///
+/// Returns 0 for every command except [`MembarrierCmd::Query`], whose result
+/// is better obtained with [`membarrier_query`].
+///
+/// # See Also
+/// [membarrier(2)](https://man7.org/linux/man-pages/man2/membarrier.2.html)
+pub fn membarrier(cmd: MembarrierCmd) -> Result<c_int> {
+    let flags: c_uint = 0;
+    let cpu_id: c_int = 0;
+    let res = unsafe {
+        libc::syscall(libc::SYS_membarrier, cmd as c_int, flags, cpu_id)
+    };
+
+    Errno::result(res).map(|r| r as c_int)
+}
+
+/// Get the set of commands supported by the running kernel.
+pub fn membarrier_query() -> Result<MembarrierCmdSet> {
+    membarrier(MembarrierCmd::Query).map(MembarrierCmdSet::from_bits_truncate)
+}
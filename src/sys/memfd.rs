@@ -114,3 +114,62 @@ pub fn memfd_create<P: NixPath + ?Sized>(
 
     Errno::result(res).map(|r| unsafe { OwnedFd::from_raw_fd(r as RawFd) })
 }
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        target_arch = "aarch64",
+        target_arch = "arm",
+        target_arch = "powerpc64",
+        target_arch = "s390x",
+        target_arch = "x86",
+        target_arch = "x86_64",
+    )
+))]
+libc_bitflags!(
+    /// Options that change the behavior of [`memfd_secret`].
+    pub struct MFdSecretFlags: libc::c_uint {
+        /// Set the close-on-exec ([`FD_CLOEXEC`]) flag on the new file descriptor.
+        ///
+        /// [`FD_CLOEXEC`]: crate::fcntl::FdFlag::FD_CLOEXEC
+        O_CLOEXEC as libc::c_uint;
+    }
+);
+
+/// Creates an anonymous file whose memory is only accessible to the processes
+/// that map it, and return a file-descriptor to it.
+///
+/// The file must be sized with [`ftruncate`] and then mapped with
+/// [`mmap`]; it can't be read or written with `read`/`write`.  The mapped pages
+/// are removed from the kernel's direct map, are locked in memory and are
+/// never swapped out or included in core dumps, which makes them suitable for
+/// storing keys.  Because the kernel itself can't access these pages, passing
+/// a pointer into such a mapping to system calls like `read`, `write` or
+/// `sendmsg` fails with `EFAULT`.
+///
+/// Available since Linux 5.14.  Fails with `ENOSYS` on older kernels, and on
+/// newer ones where secret memory is disabled, e.g. with the
+/// `secretmem.enable=0` boot parameter.
+///
+/// For more information, see [`memfd_secret(2)`].
+///
+/// [`ftruncate`]: crate::unistd::ftruncate
+/// [`mmap`]: crate::sys::mman::mmap
+/// [`memfd_secret(2)`]: https://man7.org/linux/man-pages/man2/memfd_secret.2.html
+#[cfg(all(
+    target_os = "linux",
+    any(
+        target_arch = "aarch64",
+        target_arch = "arm",
+        target_arch = "powerpc64",
+        target_arch = "s390x",
+        target_arch = "x86",
+        target_arch = "x86_64",
+    )
+))]
+#[inline]
+pub fn memfd_secret(flags: MFdSecretFlags) -> Result<OwnedFd> {
+    let res = unsafe { libc::syscall(libc::SYS_memfd_secret, flags.bits()) };
+
+    Errno::result(res).map(|r| unsafe { OwnedFd::from_raw_fd(r as RawFd) })
+}
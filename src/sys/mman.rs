@@ -560,6 +560,59 @@ pub unsafe fn madvise(
     }
 }
 
+/// NUMA memory policy mode for [`mbind`].
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(i32)]
+#[non_exhaustive]
+pub enum MPol {
+    /// Remove any policy from the range, falling back to the thread's
+    /// default policy.
+    Default = libc::MPOL_DEFAULT,
+    /// Allocate from the preferred node, falling back to others when it is
+    /// low on free memory.
+    Preferred = libc::MPOL_PREFERRED,
+    /// Restrict allocations to the given nodes.
+    Bind = libc::MPOL_BIND,
+    /// Interleave allocations page by page across the given nodes.
+    Interleave = libc::MPOL_INTERLEAVE,
+}
+
+/// Set the NUMA memory policy of a memory range.
+///
+/// `nodemask` is a bit mask of NUMA nodes: bit `n % 64` of `nodemask[n / 64]`
+/// selects node `n`.  It must be empty for [`MPol::Default`].
+///
+/// # Safety
+///
+/// `addr` must meet all the requirements described in the [`mbind(2)`] man
+/// page.
+///
+/// [`mbind(2)`]: https://man7.org/linux/man-pages/man2/mbind.2.html
+#[cfg(target_os = "linux")]
+pub unsafe fn mbind(
+    addr: NonNull<c_void>,
+    len: size_t,
+    mode: MPol,
+    nodemask: &[u64],
+) -> Result<()> {
+    // The kernel only looks at the first `maxnode - 1` bits.
+    let maxnode = nodemask.len() as libc::c_ulong * 64 + 1;
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_mbind,
+            addr.as_ptr(),
+            len,
+            mode as c_int,
+            nodemask.as_ptr(),
+            maxnode,
+            0 as libc::c_uint,
+        )
+    };
+
+    Errno::result(res).map(drop)
+}
+
 /// Set protection of memory mapping.
 ///
 /// See [`mprotect(3)`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/mprotect.html) for
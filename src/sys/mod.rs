@@ -43,6 +43,12 @@ feature! {
 #[macro_use]
 pub mod ioctl;
 
+#[cfg(target_os = "linux")]
+feature! {
+    #![feature = "mman"]
+    pub mod membarrier;
+}
+
 #[cfg(any(linux_android, target_os = "freebsd"))]
 feature! {
     #![feature = "fs"]
@@ -85,6 +91,12 @@ feature! {
     pub mod quota;
 }
 
+#[cfg(any(linux_android, apple_targets, freebsdlike, netbsdlike, solarish))]
+feature! {
+    #![feature = "random"]
+    pub mod random;
+}
+
 #[cfg(any(target_os = "linux", netbsdlike))]
 feature! {
     #![feature = "reboot"]
@@ -163,6 +175,12 @@ feature! {
     pub mod uio;
 }
 
+#[cfg(target_os = "linux")]
+feature! {
+    #![feature = "mman"]
+    pub mod userfaultfd;
+}
+
 feature! {
     #![feature = "feature"]
     pub mod utsname;
@@ -173,6 +191,12 @@ feature! {
     pub mod wait;
 }
 
+#[cfg(any(linux_android, apple_targets))]
+feature! {
+    #![feature = "fs"]
+    pub mod xattr;
+}
+
 #[cfg(linux_android)]
 feature! {
     #![feature = "inotify"]
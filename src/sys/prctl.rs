@@ -9,7 +9,7 @@ use crate::errno::Errno;
 use crate::sys::signal::Signal;
 use crate::Result;
 
-use libc::{c_int, c_ulong, c_void};
+use libc::{c_int, c_uint, c_ulong, c_void};
 use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
 use std::num::NonZeroUsize;
@@ -205,6 +205,104 @@ pub fn get_no_new_privs() -> Result<bool> {
     prctl_get_bool(libc::PR_GET_NO_NEW_PRIVS)
 }
 
+/// Get the secure computing mode of the calling thread.
+///
+/// Returns `SECCOMP_MODE_DISABLED` or `SECCOMP_MODE_FILTER`.  A thread in
+/// strict mode is killed by `SIGKILL` instead of making this call.
+pub fn get_seccomp() -> Result<c_uint> {
+    let res = unsafe { libc::prctl(libc::PR_GET_SECCOMP, 0, 0, 0, 0) };
+
+    Errno::result(res).map(|res| res as c_uint)
+}
+
+/// Put the calling thread in strict secure computing mode.
+///
+/// Afterwards, the only system calls the thread may make are `read`, `write`,
+/// `_exit` (but not `exit_group`) and `sigreturn`.  Any other system call
+/// kills the thread with `SIGKILL`.  There is no way back.
+///
+/// Note that the normal Rust process exit path calls `exit_group`, so a
+/// thread in strict mode must terminate with a raw `SYS_exit` system call.
+pub fn set_seccomp_strict() -> Result<()> {
+    let res = unsafe {
+        libc::prctl(
+            libc::PR_SET_SECCOMP,
+            libc::SECCOMP_MODE_STRICT as c_ulong,
+            0,
+            0,
+            0,
+        )
+    };
+
+    Errno::result(res).map(drop)
+}
+
+libc_bitflags! {
+    /// Flags for [`seccomp`] with [`SECCOMP_SET_MODE_FILTER`](SeccompOperation::SECCOMP_SET_MODE_FILTER).
+    pub struct SeccompFilterFlags: c_ulong {
+        /// Apply the filter to all threads of the calling process.
+        SECCOMP_FILTER_FLAG_TSYNC;
+        /// Log all filter actions except `SECCOMP_RET_ALLOW`.
+        SECCOMP_FILTER_FLAG_LOG;
+        /// Disable the Speculative Store Bypass mitigation.
+        SECCOMP_FILTER_FLAG_SPEC_ALLOW;
+    }
+}
+
+/// Operations for [`seccomp`], along with their arguments.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum SeccompOperation<'a> {
+    /// Enter strict mode, like [`set_seccomp_strict`].  Flags must be empty.
+    SECCOMP_SET_MODE_STRICT,
+    /// Install a classic BPF program that filters the system calls of the
+    /// calling thread.
+    ///
+    /// Unless the thread has `CAP_SYS_ADMIN` in its user namespace, it must
+    /// have called [`set_no_new_privs`] first, otherwise this fails with
+    /// `EACCES`.
+    SECCOMP_SET_MODE_FILTER(&'a [libc::sock_filter]),
+}
+
+/// Change the secure computing state of the calling thread.
+///
+/// On success, returns 0.  With `SECCOMP_FILTER_FLAG_TSYNC`, if another
+/// thread can't be synchronized, returns that thread's ID instead.
+///
+/// # See Also
+/// [seccomp(2)](https://man7.org/linux/man-pages/man2/seccomp.2.html)
+pub fn seccomp(
+    operation: SeccompOperation<'_>,
+    flags: SeccompFilterFlags,
+) -> Result<c_int> {
+    let res = match operation {
+        SeccompOperation::SECCOMP_SET_MODE_STRICT => unsafe {
+            libc::syscall(
+                libc::SYS_seccomp,
+                libc::SECCOMP_SET_MODE_STRICT,
+                flags.bits(),
+                std::ptr::null::<c_void>(),
+            )
+        },
+        SeccompOperation::SECCOMP_SET_MODE_FILTER(filter) => {
+            let prog = libc::sock_fprog {
+                len: filter.len().try_into().map_err(|_| Errno::EINVAL)?,
+                filter: filter.as_ptr().cast_mut(),
+            };
+            unsafe {
+                libc::syscall(
+                    libc::SYS_seccomp,
+                    libc::SECCOMP_SET_MODE_FILTER,
+                    flags.bits(),
+                    &prog as *const libc::sock_fprog,
+                )
+            }
+        }
+    };
+
+    Errno::result(res).map(|res| res as c_int)
+}
+
 /// Set the state of the "THP disable" flag for the calling thread. Setting this disables
 /// transparent huge pages.
 pub fn set_thp_disable(flag: bool) -> Result<()> {
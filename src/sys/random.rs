@@ -0,0 +1,139 @@
+//! Obtain random bytes from the operating system.
+//!
+//! Two backends are provided: [`getrandom`], available on Linux and most
+//! BSDs, and [`getentropy`], the more widely available OpenBSD interface.
+//! Both implement [`RandomSource`], and [`default_source`] picks the best
+//! one for the current platform, so that callers need not `cfg` on them.
+use crate::errno::Errno;
+use crate::Result;
+use libc::c_void;
+
+/// A source of cryptographically secure random bytes.
+pub trait RandomSource {
+    /// Fill all of `buf` with random bytes.
+    fn fill(&self, buf: &mut [u8]) -> Result<()>;
+}
+
+#[cfg(any(linux_android, freebsdlike, solarish, target_os = "netbsd"))]
+libc_bitflags! {
+    /// Options for [`getrandom`].
+    pub struct GetRandomFlags: libc::c_uint {
+        /// Fail with `EAGAIN` instead of blocking when no entropy is
+        /// available yet.
+        GRND_NONBLOCK;
+        /// Draw from the `/dev/random` pool rather than `/dev/urandom`.
+        GRND_RANDOM;
+        /// Return possibly non-cryptographic randomness without blocking,
+        /// even if the entropy pool is not yet initialized.
+        #[cfg(not(solarish))]
+        GRND_INSECURE;
+    }
+}
+
+/// Fill `buf` with random bytes, returning how many were written.
+///
+/// Reads of more than 256 bytes may be interrupted by a signal, or return
+/// fewer bytes than requested.  [`GetRandom`] takes care of both.
+///
+/// # See Also
+/// [getrandom(2)](https://man7.org/linux/man-pages/man2/getrandom.2.html)
+#[cfg(any(linux_android, freebsdlike, solarish, target_os = "netbsd"))]
+pub fn getrandom(buf: &mut [u8], flags: GetRandomFlags) -> Result<usize> {
+    let res = unsafe {
+        libc::getrandom(
+            buf.as_mut_ptr() as *mut c_void,
+            buf.len(),
+            flags.bits(),
+        )
+    };
+
+    Errno::result(res).map(|r| r as usize)
+}
+
+/// Fill `buf` with random bytes.
+///
+/// `buf` may be at most 256 bytes long; [`GetEntropy`] lifts that limit.
+///
+/// # See Also
+/// [getentropy(3)](https://man.openbsd.org/getentropy.3)
+#[cfg(any(
+    apple_targets,
+    freebsdlike,
+    netbsdlike,
+    solarish,
+    target_os = "android",
+    all(target_os = "linux", target_env = "gnu")
+))]
+pub fn getentropy(buf: &mut [u8]) -> Result<()> {
+    let res =
+        unsafe { libc::getentropy(buf.as_mut_ptr() as *mut c_void, buf.len()) };
+
+    Errno::result(res).map(drop)
+}
+
+/// [`RandomSource`] backed by [`getrandom`].
+///
+/// Blocks until the system's entropy pool has been initialized.
+#[cfg(any(linux_android, freebsdlike, solarish, target_os = "netbsd"))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct GetRandom;
+
+#[cfg(any(linux_android, freebsdlike, solarish, target_os = "netbsd"))]
+impl RandomSource for GetRandom {
+    fn fill(&self, mut buf: &mut [u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match getrandom(buf, GetRandomFlags::empty()) {
+                Ok(n) => buf = &mut buf[n..],
+                Err(Errno::EINTR) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// [`RandomSource`] backed by [`getentropy`].
+#[cfg(any(
+    apple_targets,
+    freebsdlike,
+    netbsdlike,
+    solarish,
+    target_os = "android",
+    all(target_os = "linux", target_env = "gnu")
+))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct GetEntropy;
+
+#[cfg(any(
+    apple_targets,
+    freebsdlike,
+    netbsdlike,
+    solarish,
+    target_os = "android",
+    all(target_os = "linux", target_env = "gnu")
+))]
+impl RandomSource for GetEntropy {
+    fn fill(&self, buf: &mut [u8]) -> Result<()> {
+        buf.chunks_mut(256).try_for_each(getentropy)
+    }
+}
+
+/// Return the preferred [`RandomSource`] for this platform.
+///
+/// This is [`GetRandom`] where `getrandom` exists, and [`GetEntropy`]
+/// elsewhere.
+pub fn default_source() -> impl RandomSource {
+    #[cfg(any(linux_android, freebsdlike, solarish, target_os = "netbsd"))]
+    {
+        GetRandom
+    }
+    #[cfg(not(any(
+        linux_android,
+        freebsdlike,
+        solarish,
+        target_os = "netbsd"
+    )))]
+    {
+        GetEntropy
+    }
+}
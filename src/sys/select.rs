@@ -20,11 +20,12 @@ pub struct FdSet<'fd> {
     _fd: std::marker::PhantomData<BorrowedFd<'fd>>,
 }
 
+fn fd_valid(fd: RawFd) -> bool {
+    usize::try_from(fd).map_or(false, |fd| fd < FD_SETSIZE)
+}
+
 fn assert_fd_valid(fd: RawFd) {
-    assert!(
-        usize::try_from(fd).map_or(false, |fd| fd < FD_SETSIZE),
-        "fd must be in the range 0..FD_SETSIZE",
-    );
+    assert!(fd_valid(fd), "fd must be in the range 0..FD_SETSIZE");
 }
 
 impl<'fd> FdSet<'fd> {
@@ -40,6 +41,59 @@ impl<'fd> FdSet<'fd> {
         }
     }
 
+    /// Create an `FdSet` containing every file descriptor yielded by `fds`.
+    ///
+    /// Unlike [`insert`](FdSet::insert), this does not panic if a file
+    /// descriptor is too large to be stored in an `FdSet`.  It returns
+    /// `EINVAL` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::os::unix::io::{AsFd, AsRawFd};
+    /// # use nix::sys::select::FdSet;
+    /// # use nix::unistd::pipe;
+    /// let (r1, _w1) = pipe().unwrap();
+    /// let (r2, _w2) = pipe().unwrap();
+    /// let set = FdSet::try_from_iter([r1.as_fd(), r2.as_fd()]).unwrap();
+    /// assert_eq!(
+    ///     set.highest().map(|fd| fd.as_raw_fd()),
+    ///     Some(r2.as_raw_fd())
+    /// );
+    /// ```
+    pub fn try_from_iter<I>(fds: I) -> Result<FdSet<'fd>>
+    where
+        I: IntoIterator<Item = BorrowedFd<'fd>>,
+    {
+        let mut set = FdSet::new();
+        for fd in fds {
+            set.try_insert(fd)?;
+        }
+        Ok(set)
+    }
+
+    /// Add all of `fds` to an `FdSet`.
+    ///
+    /// If any of the file descriptors is too large to be stored in an
+    /// `FdSet`, returns `EINVAL` and leaves the set unchanged.
+    pub fn insert_all(&mut self, fds: &[BorrowedFd<'fd>]) -> Result<()> {
+        if !fds.iter().all(|fd| fd_valid(fd.as_raw_fd())) {
+            return Err(Errno::EINVAL);
+        }
+        for fd in fds {
+            self.insert(*fd);
+        }
+        Ok(())
+    }
+
+    fn try_insert(&mut self, fd: BorrowedFd<'fd>) -> Result<()> {
+        if !fd_valid(fd.as_raw_fd()) {
+            return Err(Errno::EINVAL);
+        }
+        self.insert(fd);
+        Ok(())
+    }
+
     /// Add a file descriptor to an `FdSet`
     pub fn insert(&mut self, fd: BorrowedFd<'fd>) {
         assert_fd_valid(fd.as_raw_fd());
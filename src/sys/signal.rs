@@ -118,83 +118,25 @@ libc_enum! {
     impl TryFrom<i32>
 }
 
+/// Parses a signal the way `kill -s` does.
+///
+/// Names are matched case-insensitively, with or without the `SIG` prefix, so
+/// `"SIGTERM"`, `"TERM"` and `"term"` all give [`Signal::SIGTERM`].  A decimal
+/// signal number, such as `"9"`, is also accepted.
 #[cfg(feature = "signal")]
 impl FromStr for Signal {
     type Err = Error;
     fn from_str(s: &str) -> Result<Signal> {
-        Ok(match s {
-            "SIGHUP" => Signal::SIGHUP,
-            "SIGINT" => Signal::SIGINT,
-            "SIGQUIT" => Signal::SIGQUIT,
-            "SIGILL" => Signal::SIGILL,
-            "SIGTRAP" => Signal::SIGTRAP,
-            "SIGABRT" => Signal::SIGABRT,
-            "SIGBUS" => Signal::SIGBUS,
-            "SIGFPE" => Signal::SIGFPE,
-            "SIGKILL" => Signal::SIGKILL,
-            "SIGUSR1" => Signal::SIGUSR1,
-            "SIGSEGV" => Signal::SIGSEGV,
-            "SIGUSR2" => Signal::SIGUSR2,
-            "SIGPIPE" => Signal::SIGPIPE,
-            "SIGALRM" => Signal::SIGALRM,
-            "SIGTERM" => Signal::SIGTERM,
-            #[cfg(all(
-                any(
-                    linux_android,
-                    target_os = "emscripten",
-                    target_os = "fuchsia",
-                ),
-                not(any(
-                    target_arch = "mips",
-                    target_arch = "mips32r6",
-                    target_arch = "mips64",
-                    target_arch = "mips64r6",
-                    target_arch = "sparc",
-                    target_arch = "sparc64"
-                ))
-            ))]
-            "SIGSTKFLT" => Signal::SIGSTKFLT,
-            "SIGCHLD" => Signal::SIGCHLD,
-            "SIGCONT" => Signal::SIGCONT,
-            "SIGSTOP" => Signal::SIGSTOP,
-            "SIGTSTP" => Signal::SIGTSTP,
-            "SIGTTIN" => Signal::SIGTTIN,
-            "SIGTTOU" => Signal::SIGTTOU,
-            "SIGURG" => Signal::SIGURG,
-            "SIGXCPU" => Signal::SIGXCPU,
-            "SIGXFSZ" => Signal::SIGXFSZ,
-            "SIGVTALRM" => Signal::SIGVTALRM,
-            "SIGPROF" => Signal::SIGPROF,
-            "SIGWINCH" => Signal::SIGWINCH,
-            #[cfg(not(target_os = "haiku"))]
-            "SIGIO" => Signal::SIGIO,
-            #[cfg(any(
-                linux_android,
-                target_os = "emscripten",
-                target_os = "fuchsia",
-            ))]
-            "SIGPWR" => Signal::SIGPWR,
-            "SIGSYS" => Signal::SIGSYS,
-            #[cfg(not(any(
-                linux_android,
-                target_os = "emscripten",
-                target_os = "fuchsia",
-                target_os = "redox",
-                target_os = "haiku"
-            )))]
-            "SIGEMT" => Signal::SIGEMT,
-            #[cfg(not(any(
-                linux_android,
-                target_os = "emscripten",
-                target_os = "fuchsia",
-                target_os = "redox",
-                target_os = "aix",
-                target_os = "haiku",
-                target_os = "solaris"
-            )))]
-            "SIGINFO" => Signal::SIGINFO,
-            _ => return Err(Errno::EINVAL),
-        })
+        if let Ok(signum) = s.parse::<libc::c_int>() {
+            return Signal::try_from(signum);
+        }
+        let name = match s.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("SIG") => &s[3..],
+            _ => s,
+        };
+        Signal::iterator()
+            .find(|signal| signal.as_str()[3..].eq_ignore_ascii_case(name))
+            .ok_or(Errno::EINVAL)
     }
 }
 
@@ -409,6 +351,10 @@ impl Iterator for SignalIterator {
 
 impl Signal {
     /// Iterate through all signals defined by this OS
+    ///
+    /// This includes `SIGKILL` and `SIGSTOP`, which can be neither caught,
+    /// blocked nor ignored; [`sigaction`] fails with `EINVAL` for them.  Skip
+    /// them when installing a handler for every signal.
     pub const fn iterator() -> SignalIterator {
         SignalIterator{next: 0}
     }
@@ -499,6 +445,9 @@ pub struct SigSet {
 
 impl SigSet {
     /// Initialize to include all signals.
+    ///
+    /// The set includes `SIGKILL` and `SIGSTOP`, but blocking them with
+    /// [`SigSet::thread_block`] or [`sigprocmask`] is silently ignored.
     #[doc(alias("sigfillset"))]
     pub fn all() -> SigSet {
         let mut sigset = mem::MaybeUninit::uninit();
@@ -580,18 +529,22 @@ impl SigSet {
         Ok(unsafe{ SigSet{sigset: oldmask.assume_init()}})
     }
 
+    /// Adds the set of signals to the signal mask for the calling thread,
+    /// until the returned guard is dropped.
+    ///
+    /// This is the same as [`SigMaskGuard::new`] with
+    /// [`SigmaskHow::SIG_BLOCK`].
+    pub fn block_scoped(&self) -> Result<SigMaskGuard> {
+        SigMaskGuard::new(SigmaskHow::SIG_BLOCK, self)
+    }
+
     /// Suspends execution of the calling thread until one of the signals in the
     /// signal mask becomes pending, and returns the accepted signal.
+    ///
+    /// This is the same as [`sigwait`].
     #[cfg(not(target_os = "redox"))] // RedoxFS does not yet support sigwait
     pub fn wait(&self) -> Result<Signal> {
-        use std::convert::TryFrom;
-
-        let mut signum = mem::MaybeUninit::uninit();
-        let res = unsafe { libc::sigwait(&self.sigset as *const libc::sigset_t, signum.as_mut_ptr()) };
-
-        Errno::result(res).map(|_| unsafe {
-            Signal::try_from(signum.assume_init()).unwrap()
-        })
+        sigwait(self)
     }
 
     /// Wait for a signal
@@ -893,8 +846,9 @@ impl SigAction {
 
 /// Changes the action taken by a process on receipt of a specific signal.
 ///
-/// `signal` can be any signal except `SIGKILL` or `SIGSTOP`. On success, it returns the previous
-/// action for the given signal. If `sigaction` fails, no new signal handler is installed.
+/// `signal` can be any signal except `SIGKILL` or `SIGSTOP`, for which it fails with `EINVAL`. On
+/// success, it returns the previous action for the given signal. If `sigaction` fails, no new
+/// signal handler is installed.
 ///
 /// # Safety
 ///
@@ -1051,6 +1005,104 @@ pub fn sigprocmask(how: SigmaskHow, set: Option<&SigSet>, oldset: Option<&mut Si
     Errno::result(res).map(drop)
 }
 
+/// Examine and change blocked signals, returning the previous mask.
+///
+/// This is [`sigprocmask`] with `set` always given.  The returned mask can be
+/// passed back with [`SigmaskHow::SIG_SETMASK`] to restore it, which
+/// [`SigMaskGuard`] does automatically.
+///
+/// For more information see the [`sigprocmask` man
+/// pages](https://pubs.opengroup.org/onlinepubs/9699919799/functions/sigprocmask.html).
+pub fn sigprocmask_swap(how: SigmaskHow, set: &SigSet) -> Result<SigSet> {
+    let mut oldset = SigSet::empty();
+    sigprocmask(how, Some(set), Some(&mut oldset))?;
+    Ok(oldset)
+}
+
+/// Restores the signal mask of the calling thread when dropped.
+///
+/// This protects a critical section from signal delivery without having to
+/// restore the mask by hand on every exit path, including unwinding from a
+/// panic.  [`SigSet::block_scoped`] is a shorthand for the common case:
+///
+/// ```
+/// # use nix::sys::signal::*;
+/// let set = SigSet::from(Signal::SIGINT);
+/// {
+///     let _guard = SigMaskGuard::new(SigmaskHow::SIG_BLOCK, &set).unwrap();
+///     // SIGINT stays pending until `_guard` is dropped
+/// }
+/// ```
+///
+/// The signal mask is per thread, so the guard can't be sent to another one.
+#[derive(Debug)]
+#[must_use = "the previous signal mask is restored as soon as the guard is dropped"]
+pub struct SigMaskGuard {
+    oldmask: SigSet,
+    // The mask must be restored by the thread that changed it
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl SigMaskGuard {
+    /// Change the signal mask as with [`SigSet::thread_swap_mask`], and return
+    /// a guard that restores the previous mask when dropped.
+    pub fn new(how: SigmaskHow, set: &SigSet) -> Result<SigMaskGuard> {
+        set.thread_swap_mask(how).map(|oldmask| SigMaskGuard {
+            oldmask,
+            _not_send: std::marker::PhantomData,
+        })
+    }
+
+    /// The signal mask that will be restored.
+    pub fn oldmask(&self) -> &SigSet {
+        &self.oldmask
+    }
+}
+
+impl Drop for SigMaskGuard {
+    fn drop(&mut self) {
+        // Can only fail with EINVAL for an invalid `how`
+        let _ = self.oldmask.thread_set_mask();
+    }
+}
+
+/// Return the set of signals that are pending for delivery to the calling
+/// thread, i.e. raised while blocked.
+///
+/// For more information see the [`sigpending` man
+/// page](https://pubs.opengroup.org/onlinepubs/9699919799/functions/sigpending.html).
+pub fn sigpending() -> Result<SigSet> {
+    let mut set = mem::MaybeUninit::uninit();
+    let res = unsafe { libc::sigpending(set.as_mut_ptr()) };
+    Errno::result(res)?;
+    Ok(unsafe { SigSet { sigset: set.assume_init() } })
+}
+
+/// Wait for one of the signals in `set` to become pending, accept it and
+/// return it.
+///
+/// The signals in `set` should be blocked in every thread beforehand, so that
+/// they aren't delivered to a handler instead.  A common pattern is to block
+/// them in the main thread before spawning any other, and to call `sigwait` in
+/// a loop from a dedicated signal-handling thread.
+///
+/// Unlike `sigwaitinfo`, this does not return any `siginfo_t` details.
+///
+/// See Also
+/// [`sigwait(3)`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/sigwait.html)
+#[cfg(not(target_os = "redox"))] // RedoxFS does not yet support sigwait
+pub fn sigwait(set: &SigSet) -> Result<Signal> {
+    let mut signum = mem::MaybeUninit::uninit();
+    // sigwait returns the error number rather than setting errno
+    let res = unsafe {
+        libc::sigwait(&set.sigset as *const libc::sigset_t, signum.as_mut_ptr())
+    };
+    if res != 0 {
+        return Err(Errno::from_raw(res));
+    }
+    Signal::try_from(unsafe { signum.assume_init() })
+}
+
 /// Send a signal to a process
 ///
 /// # Arguments
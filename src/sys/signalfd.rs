@@ -17,21 +17,46 @@
 //! signal handlers.
 use crate::errno::Errno;
 pub use crate::sys::signal::{self, SigSet};
+use crate::sys::time::TimeSpec;
+use crate::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
+use crate::unistd::Pid;
 use crate::Result;
 
 /// Information of a received signal, the return type of [`SignalFd::read_signal()`].
 pub use libc::signalfd_siginfo as siginfo;
 
 use std::mem;
-use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::io::{
+    AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd,
+};
 
 libc_bitflags! {
+    /// Flags for [`SignalFd::with_flags`].
     pub struct SfdFlags: libc::c_int {
+        /// Set `O_NONBLOCK` on the new file descriptor, so that reads
+        /// return `EAGAIN` instead of blocking.
         SFD_NONBLOCK;
+        /// Set the close-on-exec flag on the new file descriptor.
         SFD_CLOEXEC;
     }
 }
 
+/// Extension methods for [`siginfo`], which is a `libc` type.
+pub trait SiginfoExt {
+    /// The signal that was received, or `None` if it has no [`Signal`]
+    /// variant, like real-time signals.
+    ///
+    /// [`Signal`]: signal::Signal
+    fn signal(&self) -> Option<signal::Signal>;
+}
+
+impl SiginfoExt for siginfo {
+    fn signal(&self) -> Option<signal::Signal> {
+        let signo = libc::c_int::try_from(self.ssi_signo).ok()?;
+        signal::Signal::try_from(signo).ok()
+    }
+}
+
 #[deprecated(since = "0.23.0", note = "use mem::size_of::<siginfo>() instead")]
 pub const SIGNALFD_SIGINFO_SIZE: usize = mem::size_of::<siginfo>();
 
@@ -97,8 +122,9 @@ fn _signalfd<F: AsFd>(
 pub struct SignalFd(OwnedFd);
 
 impl SignalFd {
+    /// [`SignalFd::with_flags`] with `flags = SfdFlags::SFD_CLOEXEC`.
     pub fn new(mask: &SigSet) -> Result<SignalFd> {
-        Self::with_flags(mask, SfdFlags::empty())
+        Self::with_flags(mask, SfdFlags::SFD_CLOEXEC)
     }
 
     pub fn with_flags(mask: &SigSet, flags: SfdFlags) -> Result<SignalFd> {
@@ -111,6 +137,12 @@ impl SignalFd {
         self.update(mask, SfdFlags::empty())
     }
 
+    /// Read one pending signal from the file descriptor.
+    ///
+    /// Returns `Ok(None)` if the descriptor is non-blocking and no signal is
+    /// pending, so it can be used after polling the descriptor for
+    /// readiness, e.g. with [`poll`](crate::poll::poll) or an external event
+    /// loop.
     pub fn read_signal(&self) -> Result<Option<siginfo>> {
         let mut buffer = mem::MaybeUninit::<siginfo>::uninit();
 
@@ -127,6 +159,86 @@ impl SignalFd {
         }
     }
 
+    /// Wait up to `timeout` for a signal, then read it.
+    ///
+    /// Returns `Ok(None)` if no signal arrived in time.  Like
+    /// [`poll`](crate::poll::poll), this fails with `EINTR` if the wait is
+    /// interrupted by a signal that isn't in the mask.
+    pub fn read_signal_timeout(
+        &self,
+        timeout: TimeSpec,
+    ) -> Result<Option<siginfo>> {
+        let mut pfd = libc::pollfd {
+            fd: self.0.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let res = unsafe {
+            libc::ppoll(&mut pfd, 1, timeout.as_ref(), std::ptr::null())
+        };
+
+        match Errno::result(res)? {
+            0 => Ok(None),
+            _ => self.read_signal(),
+        }
+    }
+
+    /// Read as many pending signals as fit in `buf` with a single `read`.
+    ///
+    /// Returns the number of signals read into the start of `buf`.  Like
+    /// [`read_signal`](SignalFd::read_signal), returns `Ok(0)` instead of
+    /// `EAGAIN` if the descriptor is non-blocking and no signal is pending.
+    /// The kernel only ever returns whole records.
+    pub fn read_signals(&self, buf: &mut [siginfo]) -> Result<usize> {
+        let size = mem::size_of::<siginfo>();
+        let res = Errno::result(unsafe {
+            libc::read(
+                self.0.as_raw_fd(),
+                buf.as_mut_ptr().cast(),
+                mem::size_of_val(buf),
+            )
+        })
+        .map(|r| r as usize);
+        match res {
+            Ok(x) => {
+                assert_eq!(x % size, 0, "partial read on signalfd");
+                Ok(x / size)
+            }
+            Err(Errno::EAGAIN) => Ok(0),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Reap every child process that has terminated, without blocking.
+    ///
+    /// Standard signals are not queued, so a single `SIGCHLD` read from this
+    /// file descriptor may stand for several children exiting.  After reading
+    /// a `SIGCHLD` with [`read_signal`](SignalFd::read_signal), call this to
+    /// collect all of them with `waitpid(-1, WNOHANG)`.  Returns an empty
+    /// `Vec` if no child is ready to be reaped, or if there are no children at
+    /// all.
+    ///
+    /// This does not read from the file descriptor.  Note that it reaps
+    /// *any* child of the process, including ones that some other part of the
+    /// program may be waiting for.
+    pub fn drain_children(&mut self) -> Result<Vec<(Pid, WaitStatus)>> {
+        let mut reaped = Vec::new();
+        loop {
+            match waitpid(None, Some(WaitPidFlag::WNOHANG)) {
+                Ok(WaitStatus::StillAlive) | Err(Errno::ECHILD) => break,
+                Ok(status) => {
+                    let pid = status
+                        .pid()
+                        .expect("waitpid returned a status without a pid");
+                    reaped.push((pid, status));
+                }
+                Err(Errno::EINTR) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(reaped)
+    }
+
     /// Constructs a `SignalFd` wrapping an existing `OwnedFd`.
     ///
     /// # Safety
@@ -155,6 +267,16 @@ impl AsRawFd for SignalFd {
         self.0.as_raw_fd()
     }
 }
+impl IntoRawFd for SignalFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
+    }
+}
+impl FromRawFd for SignalFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        Self(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+}
 
 impl From<SignalFd> for OwnedFd {
     fn from(value: SignalFd) -> Self {
@@ -162,13 +284,17 @@ impl From<SignalFd> for OwnedFd {
     }
 }
 
+/// Iterate over pending signals.
+///
+/// Each item is the result of one [`SignalFd::read_signal`] call.  Iteration
+/// ends once no signal is pending, which only happens if the descriptor was
+/// created with [`SfdFlags::SFD_NONBLOCK`]; on a blocking descriptor `next`
+/// waits for the next signal instead.  Read errors are yielded as `Err` items
+/// rather than ending the iteration.
 impl Iterator for SignalFd {
-    type Item = siginfo;
+    type Item = Result<siginfo>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.read_signal() {
-            Ok(Some(sig)) => Some(sig),
-            Ok(None) | Err(_) => None,
-        }
+        self.read_signal().transpose()
     }
 }
@@ -413,6 +413,11 @@ impl UnixAddr {
     }
 
     /// Create a new `sockaddr_un` representing an "unnamed" unix socket address.
+    ///
+    /// Binding a socket to this address makes the kernel autobind it to a
+    /// unique abstract address: a NUL byte followed by five hex digits.  Read
+    /// it back with [`getsockname`](super::getsockname) and
+    /// [`as_abstract`](UnixAddr::as_abstract).
     #[cfg(linux_android)]
     pub fn new_unnamed() -> UnixAddr {
         let ret = libc::sockaddr_un {
@@ -286,29 +286,75 @@ libc_bitflags! {
     }
 }
 
-libc_bitflags! {
+::bitflags::bitflags! {
     /// Additional socket options
+    ///
+    /// `SOCK_NONBLOCK` and `SOCK_CLOEXEC` are available on every platform.
+    /// Where the kernel doesn't accept them as part of the socket type,
+    /// [`socket`] and [`socketpair`] apply them with `fcntl(2)` right after
+    /// creating the sockets.  That isn't atomic: another thread that calls
+    /// `fork` in the meantime may still leak the new sockets into its child.
+    #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    #[repr(transparent)]
     pub struct SockFlag: c_int {
         /// Set non-blocking mode on the new socket
         #[cfg(any(linux_android,
                   freebsdlike,
                   netbsdlike,
                   solarish))]
-        SOCK_NONBLOCK;
+        const SOCK_NONBLOCK = libc::SOCK_NONBLOCK;
+        /// Set non-blocking mode on the new socket, with `fcntl(2)`
+        #[cfg(not(any(linux_android,
+                      freebsdlike,
+                      netbsdlike,
+                      solarish)))]
+        const SOCK_NONBLOCK = libc::O_NONBLOCK;
         /// Set close-on-exec on the new descriptor
         #[cfg(any(linux_android,
                   freebsdlike,
                   netbsdlike,
                   solarish))]
-        SOCK_CLOEXEC;
+        const SOCK_CLOEXEC = libc::SOCK_CLOEXEC;
+        /// Set close-on-exec on the new descriptor, with `fcntl(2)`
+        #[cfg(not(any(linux_android,
+                      freebsdlike,
+                      netbsdlike,
+                      solarish)))]
+        const SOCK_CLOEXEC = libc::O_CLOEXEC;
         /// Return `EPIPE` instead of raising `SIGPIPE`
         #[cfg(target_os = "netbsd")]
-        SOCK_NOSIGPIPE;
+        const SOCK_NOSIGPIPE = libc::SOCK_NOSIGPIPE;
         /// For domains `AF_INET(6)`, only allow `connect(2)`, `sendto(2)`, or `sendmsg(2)`
         /// to the DNS port (typically 53)
         #[cfg(target_os = "openbsd")]
-        SOCK_DNS;
+        const SOCK_DNS = libc::SOCK_DNS;
+    }
+}
+
+/// The flags that the kernel doesn't accept as part of the socket type, and
+/// that must be applied with `fcntl` instead.
+#[cfg(not(any(linux_android, freebsdlike, netbsdlike, solarish)))]
+const EMULATED_SOCK_FLAGS: SockFlag =
+    SockFlag::SOCK_NONBLOCK.union(SockFlag::SOCK_CLOEXEC);
+#[cfg(any(linux_android, freebsdlike, netbsdlike, solarish))]
+const EMULATED_SOCK_FLAGS: SockFlag = SockFlag::empty();
+
+/// Apply the emulated `SOCK_CLOEXEC` and `SOCK_NONBLOCK` in `flags` to a newly
+/// created socket.
+#[cfg(not(any(linux_android, freebsdlike, netbsdlike, solarish)))]
+fn set_emulated_sock_flags<Fd: AsFd>(fd: &Fd, flags: SockFlag) -> Result<()> {
+    let fd = fd.as_fd().as_raw_fd();
+    if flags.contains(SockFlag::SOCK_CLOEXEC) {
+        let res = unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+        Errno::result(res)?;
+    }
+    if flags.contains(SockFlag::SOCK_NONBLOCK) {
+        let fl = Errno::result(unsafe { libc::fcntl(fd, libc::F_GETFL) })?;
+        let res =
+            unsafe { libc::fcntl(fd, libc::F_SETFL, fl | libc::O_NONBLOCK) };
+        Errno::result(res)?;
     }
+    Ok(())
 }
 
 libc_bitflags! {
@@ -317,6 +363,11 @@ libc_bitflags! {
         /// Sends or requests out-of-band data on sockets that support this notion
         /// (e.g., of type [`Stream`](enum.SockType.html)); the underlying protocol must also
         /// support out-of-band data.
+        ///
+        /// For TCP, only the last byte passed to a send with this flag is
+        /// urgent; it's received with `MSG_OOB` unless
+        /// [`OobInline`](sockopt/struct.OobInline.html) is set on the
+        /// receiving socket.
         MSG_OOB;
         /// Peeks at an incoming message. The data is treated as unread and the next
         /// [`recv()`](fn.recv.html)
@@ -368,7 +419,19 @@ libc_bitflags! {
         MSG_CMSG_CLOEXEC;
         /// Requests not to send `SIGPIPE` errors when the other end breaks the connection.
         /// (For more details, see [send(2)](https://linux.die.net/man/2/send)).
+        ///
+        /// The send will fail with `EPIPE` instead.  Apple platforms don't
+        /// support this flag for sending, so there [`send`](fn.send.html),
+        /// [`sendto`](fn.sendto.html) and [`sendmsg`](fn.sendmsg.html) emulate it by setting the `sockopt::NoSigPipe` socket
+        /// option for the duration of the call.  That isn't atomic: other
+        /// threads sending on the same socket meanwhile won't get `SIGPIPE`
+        /// either.
+        ///
+        /// The portable way to avoid `SIGPIPE` altogether is to ignore it for
+        /// the whole process, or to set `sockopt::NoSigPipe` once on every
+        /// socket where it's available.
         #[cfg(any(linux_android,
+                  apple_targets,
                   freebsdlike,
                   solarish,
                   netbsdlike,
@@ -385,6 +448,17 @@ libc_bitflags! {
         /// Indicates that this message is not a user message but an SCTP notification.
         #[cfg(target_os = "linux")]
         MSG_NOTIFICATION;
+        /// Sends data in the SYN of a TCP Fast Open connection, combining
+        /// `connect` and the first send into a single call. Only used with
+        /// [`sendto`](fn.sendto.html) and [`sendmsg`](fn.sendmsg.html) on an
+        /// unconnected TCP socket, with the destination address given.
+        ///
+        /// If no Fast Open cookie for the peer is cached yet, a regular
+        /// handshake is made and the data is sent once it completes.  See
+        /// [`TcpFastOpen`](sockopt/struct.TcpFastOpen.html) for the sysctl that
+        /// has to allow this.
+        #[cfg(linux_android)]
+        MSG_FASTOPEN;
     }
 }
 
@@ -699,9 +773,15 @@ impl<S> RecvMsg<'_, '_, S> {
     /// Iterate over the valid control messages pointed to by this msghdr. If
     /// allocated space for CMSGs was too small it is not safe to iterate,
     /// instead return an `Error::ENOBUFS` error.
+    ///
+    /// The file descriptors in [`ControlMessageOwned::ScmRights`] messages
+    /// belong to the caller, who should wrap them in `OwnedFd`s as they are
+    /// returned.  When `ENOBUFS` is returned instead, the descriptors that did
+    /// fit in the buffer are closed, so that none of them is leaked.
     pub fn cmsgs(&self) -> Result<CmsgIterator> {
 
         if self.mhdr.msg_flags & MSG_CTRUNC == MSG_CTRUNC {
+            self.close_truncated_fds();
             return Err(Errno::ENOBUFS);
         }
 
@@ -710,6 +790,57 @@ impl<S> RecvMsg<'_, '_, S> {
             mhdr: &self.mhdr
         })
     }
+
+    /// Close the descriptors of every `SCM_RIGHTS` message that fit in a
+    /// truncated control message buffer.
+    ///
+    /// Each closed descriptor is overwritten with `-1` in the buffer, so that
+    /// calling [`cmsgs`][RecvMsg::cmsgs] again won't close it a second time.
+    // Clippy complains about the pointer alignment of `fdp`, not understanding
+    // that it's only used for unaligned reads and writes.
+    #[allow(clippy::cast_ptr_alignment)]
+    fn close_truncated_fds(&self) {
+        let base = self.mhdr.msg_control.cast::<u8>();
+        // The cast is not unnecessary on all platforms.
+        #[allow(clippy::unnecessary_cast)]
+        let end = base as usize + self.mhdr.msg_controllen as usize;
+        let mut cmsghdr = self.cmsghdr;
+
+        while let Some(hdr) = cmsghdr {
+            if hdr.cmsg_level == libc::SOL_SOCKET
+                && hdr.cmsg_type == libc::SCM_RIGHTS
+            {
+                let data = unsafe { CMSG_DATA(hdr) } as usize;
+                // Never read past the buffer, even if the truncated message
+                // claims to be longer.  The cast is not unnecessary on all
+                // platforms.
+                #[allow(clippy::unnecessary_cast)]
+                let hdr_end = hdr as *const _ as usize + hdr.cmsg_len as usize;
+                let len = hdr_end.min(end).saturating_sub(data);
+                for i in 0..len / mem::size_of::<RawFd>() {
+                    // Write through `msg_control`, which points to the
+                    // mutable buffer passed to `recvmsg`.
+                    let fdp = unsafe {
+                        base.add(data - base as usize)
+                            .cast::<RawFd>()
+                            .add(i)
+                    };
+                    let fd = unsafe { ptr::read_unaligned(fdp) };
+                    if fd >= 0 {
+                        // Safe because the kernel installed a new descriptor
+                        // for us, which nobody else can know about.
+                        drop(unsafe { OwnedFd::from_raw_fd(fd) });
+                        unsafe { ptr::write_unaligned(fdp, -1) };
+                    }
+                }
+            }
+            // Safe if mhdr and cmsghdr point to valid data returned by
+            // recvmsg(2)
+            cmsghdr = unsafe {
+                CMSG_NXTHDR(&self.mhdr as *const _, hdr as *const _).as_ref()
+            };
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -1721,6 +1852,12 @@ pub fn sendmsg<S>(fd: RawFd, iov: &[IoSlice<'_>], cmsgs: &[ControlMessage],
                flags: MsgFlags, addr: Option<&S>) -> Result<usize>
     where S: SockaddrLike
 {
+    #[cfg(apple_targets)]
+    if flags.contains(MsgFlags::MSG_NOSIGNAL) {
+        let flags = flags.difference(MsgFlags::MSG_NOSIGNAL);
+        return with_nosigpipe(fd, || sendmsg(fd, iov, cmsgs, flags, addr));
+    }
+
     let capacity = cmsgs.iter().map(|c| c.space()).sum();
 
     // First size the buffer needed to hold the cmsgs.  It must be zeroed,
@@ -2202,6 +2339,46 @@ pub fn recvmsg<'a, 'outer, 'inner, S>(fd: RawFd, iov: &'outer mut [IoSliceMut<'i
 
     Ok(unsafe { read_mhdr(mhdr, r, msg_controllen, address.assume_init()) })
 }
+
+/// Receive a message, along with any file descriptors passed with it.
+///
+/// This is a convenience wrapper around [`recvmsg`] for the common case of
+/// receiving `SCM_RIGHTS` control messages.  It returns the number of bytes
+/// received, the source address if any, and the received file descriptors.
+/// Other control messages are discarded.
+///
+/// If `cmsg_buffer` was too small to hold all the control messages, the
+/// descriptors that were received are closed and `ENOBUFS` is returned.
+///
+/// # Arguments
+///
+/// * `fd`:             Socket file descriptor
+/// * `iov`:            Scatter-gather list of buffers to receive the message
+/// * `cmsg_buffer`:    Space to receive ancillary data.  Should be created by
+///                     [`cmsg_space!`](../../macro.cmsg_space.html)
+/// * `flags`:          Optional flags passed directly to the operating system.
+///                     Consider `MSG_CMSG_CLOEXEC` where it is available.
+pub fn recvmsg_with_fds(
+    fd: RawFd,
+    iov: &mut [IoSliceMut<'_>],
+    cmsg_buffer: &mut [u8],
+    flags: MsgFlags,
+) -> Result<(usize, Option<SockaddrStorage>, Vec<OwnedFd>)> {
+    let msg = recvmsg::<SockaddrStorage>(fd, iov, Some(cmsg_buffer), flags)?;
+    let mut fds = Vec::new();
+    for cmsg in msg.cmsgs()? {
+        if let ControlMessageOwned::ScmRights(received) = cmsg {
+            // Safe because `msg` never escapes this function, so each
+            // descriptor is only ever returned here.
+            fds.extend(
+                received
+                    .into_iter()
+                    .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) }),
+            );
+        }
+    }
+    Ok((msg.bytes, msg.address, fds))
+}
 }
 
 /// Create an endpoint for communication
@@ -2213,6 +2390,10 @@ pub fn recvmsg<'a, 'outer, 'inner, S>(fd: RawFd, iov: &'outer mut [IoSliceMut<'i
 /// protocols may exist, in which case a particular protocol must be
 /// specified in this manner.
 ///
+/// `SOCK_CLOEXEC` and `SOCK_NONBLOCK` in `flags` are applied atomically where
+/// the kernel supports them as part of the socket type, and with a separate,
+/// non-atomic, `fcntl(2)` call everywhere else.  See [`SockFlag`].
+///
 /// [Further reading](https://pubs.opengroup.org/onlinepubs/9699919799/functions/socket.html)
 pub fn socket<T: Into<Option<SockProtocol>>>(
     domain: AddressFamily,
@@ -2229,21 +2410,24 @@ pub fn socket<T: Into<Option<SockProtocol>>>(
     // little easier to understand by separating it out. So we have to merge these bitfields
     // here.
     let mut ty = ty as c_int;
-    ty |= flags.bits();
+    ty |= flags.difference(EMULATED_SOCK_FLAGS).bits();
 
     let res = unsafe { libc::socket(domain as c_int, ty, protocol) };
 
-    match res {
-        -1 => Err(Errno::last()),
-        fd => {
-            // Safe because libc::socket returned success
-            unsafe { Ok(OwnedFd::from_raw_fd(fd)) }
-        }
-    }
+    let fd = match res {
+        -1 => return Err(Errno::last()),
+        // Safe because libc::socket returned success
+        fd => unsafe { OwnedFd::from_raw_fd(fd) },
+    };
+    #[cfg(not(any(linux_android, freebsdlike, netbsdlike, solarish)))]
+    set_emulated_sock_flags(&fd, flags)?;
+    Ok(fd)
 }
 
 /// Create a pair of connected sockets
 ///
+/// `flags` are applied to both sockets, in the same way as for [`socket`].
+///
 /// [Further reading](https://pubs.opengroup.org/onlinepubs/9699919799/functions/socketpair.html)
 pub fn socketpair<T: Into<Option<SockProtocol>>>(
     domain: AddressFamily,
@@ -2260,7 +2444,7 @@ pub fn socketpair<T: Into<Option<SockProtocol>>>(
     // little easier to understand by separating it out. So we have to merge these bitfields
     // here.
     let mut ty = ty as c_int;
-    ty |= flags.bits();
+    ty |= flags.difference(EMULATED_SOCK_FLAGS).bits();
 
     let mut fds = [-1, -1];
 
@@ -2270,7 +2454,14 @@ pub fn socketpair<T: Into<Option<SockProtocol>>>(
     Errno::result(res)?;
 
     // Safe because socketpair returned success.
-    unsafe { Ok((OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1]))) }
+    let (fd0, fd1) =
+        unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+    #[cfg(not(any(linux_android, freebsdlike, netbsdlike, solarish)))]
+    {
+        set_emulated_sock_flags(&fd0, flags)?;
+        set_emulated_sock_flags(&fd1, flags)?;
+    }
+    Ok((fd0, fd1))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -2376,6 +2567,14 @@ pub fn connect(fd: RawFd, addr: &dyn SockaddrLike) -> Result<()> {
 /// Receive data from a connection-oriented socket. Returns the number of
 /// bytes read
 ///
+/// If `flags` contains [`MsgFlags::MSG_TRUNC`] and the socket is a datagram
+/// or raw socket, the returned length is that of the whole datagram, which
+/// may be larger than `buf`.  Combined with [`MsgFlags::MSG_PEEK`] this
+/// discovers the size of the next datagram without consuming it, so the
+/// caller can grow its buffer and read it again.  On Linux, `MSG_TRUNC`
+/// without `MSG_PEEK` also reports the real length, but the datagram is
+/// consumed and any excess bytes are discarded.
+///
 /// [Further reading](https://pubs.opengroup.org/onlinepubs/9699919799/functions/recv.html)
 pub fn recv(sockfd: RawFd, buf: &mut [u8], flags: MsgFlags) -> Result<usize> {
     unsafe {
@@ -2394,10 +2593,29 @@ pub fn recv(sockfd: RawFd, buf: &mut [u8], flags: MsgFlags) -> Result<usize> {
 /// the number of bytes read and, for connectionless sockets,  the socket
 /// address of the sender.
 ///
+/// With `T` = [`SockaddrStorage`], senders of any address family can be
+/// received, and the address reports the family it actually has.  This
+/// includes a dual-stack IPv6 socket, where IPv4 senders show up as
+/// IPv4-mapped IPv6 addresses.
+///
 /// [Further reading](https://pubs.opengroup.org/onlinepubs/9699919799/functions/recvfrom.html)
 pub fn recvfrom<T: SockaddrLike>(
     sockfd: RawFd,
     buf: &mut [u8],
+) -> Result<(usize, Option<T>)> {
+    recvfrom_with_flags(sockfd, buf, MsgFlags::empty())
+}
+
+/// Like [`recvfrom`], but with `flags` passed to the system call.
+///
+/// As with [`recv`], if `flags` contains [`MsgFlags::MSG_TRUNC`] the returned
+/// length is that of the whole datagram, even when it exceeds `buf.len()`.
+///
+/// [Further reading](https://pubs.opengroup.org/onlinepubs/9699919799/functions/recvfrom.html)
+pub fn recvfrom_with_flags<T: SockaddrLike>(
+    sockfd: RawFd,
+    buf: &mut [u8],
+    flags: MsgFlags,
 ) -> Result<(usize, Option<T>)> {
     unsafe {
         let mut addr = mem::MaybeUninit::<T>::uninit();
@@ -2407,7 +2625,7 @@ pub fn recvfrom<T: SockaddrLike>(
             sockfd,
             buf.as_mut_ptr().cast(),
             buf.len() as size_t,
-            0,
+            flags.bits(),
             addr.as_mut_ptr().cast(),
             &mut len as *mut socklen_t,
         ))? as usize;
@@ -2425,6 +2643,12 @@ pub fn sendto(
     addr: &dyn SockaddrLike,
     flags: MsgFlags,
 ) -> Result<usize> {
+    #[cfg(apple_targets)]
+    if flags.contains(MsgFlags::MSG_NOSIGNAL) {
+        let flags = flags.difference(MsgFlags::MSG_NOSIGNAL);
+        return with_nosigpipe(fd, || sendto(fd, buf, addr, flags));
+    }
+
     let ret = unsafe {
         libc::sendto(
             fd,
@@ -2443,6 +2667,12 @@ pub fn sendto(
 ///
 /// [Further reading](https://pubs.opengroup.org/onlinepubs/9699919799/functions/send.html)
 pub fn send(fd: RawFd, buf: &[u8], flags: MsgFlags) -> Result<usize> {
+    #[cfg(apple_targets)]
+    if flags.contains(MsgFlags::MSG_NOSIGNAL) {
+        let flags = flags.difference(MsgFlags::MSG_NOSIGNAL);
+        return with_nosigpipe(fd, || send(fd, buf, flags));
+    }
+
     let ret = unsafe {
         libc::send(fd, buf.as_ptr().cast(), buf.len() as size_t, flags.bits())
     };
@@ -2450,6 +2680,26 @@ pub fn send(fd: RawFd, buf: &[u8], flags: MsgFlags) -> Result<usize> {
     Errno::result(ret).map(|r| r as usize)
 }
 
+/// Emulate `MSG_NOSIGNAL`, which Apple kernels don't support for sending, by
+/// calling `f` with `SO_NOSIGPIPE` set on `fd`.
+#[cfg(apple_targets)]
+fn with_nosigpipe<F>(fd: RawFd, f: F) -> Result<usize>
+where
+    F: FnOnce() -> Result<usize>,
+{
+    // Safe because the caller passed `fd` for a send
+    let borrowed = unsafe { std::os::unix::io::BorrowedFd::borrow_raw(fd) };
+    if getsockopt(&borrowed, sockopt::NoSigPipe)? {
+        return f();
+    }
+    setsockopt(&borrowed, sockopt::NoSigPipe, &true)?;
+    let res = f();
+    // This can't fail after setting the option succeeded, and the result of
+    // the send matters more.
+    let _ = setsockopt(&borrowed, sockopt::NoSigPipe, &false);
+    res
+}
+
 /*
  *
  * ===== Socket Options =====
@@ -2503,6 +2753,56 @@ pub fn setsockopt<F: AsFd, O: SetSockOpt>(
     opt.set(fd, val)
 }
 
+/// Enable TCP keepalive on `fd`, and tune it.
+///
+/// This sets `SO_KEEPALIVE` along with:
+/// - `idle`: how long the connection must be idle before the first probe is
+///   sent ([`TcpKeepIdle`](sockopt::TcpKeepIdle), or
+///   `TCP_KEEPALIVE` on Apple targets),
+/// - `interval`: the time between probes
+///   ([`TcpKeepInterval`](sockopt::TcpKeepInterval)),
+/// - `count`: how many unanswered probes drop the connection
+///   ([`TcpKeepCount`](sockopt::TcpKeepCount)).
+///
+/// The durations are rounded down to whole seconds, which is the
+/// granularity of the underlying options.  Durations that don't fit the
+/// options fail with `EINVAL`.
+///
+/// # Examples
+///
+/// ```
+/// use nix::sys::socket::set_keepalive;
+/// use std::net::TcpListener;
+/// use std::time::Duration;
+///
+/// let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+/// let idle = Duration::from_secs(60);
+/// let interval = Duration::from_secs(10);
+/// set_keepalive(&listener, idle, interval, 5).unwrap();
+/// ```
+#[cfg(all(feature = "net", any(apple_targets, freebsdlike, linux_android)))]
+#[cfg_attr(docsrs, doc(cfg(feature = "net")))]
+pub fn set_keepalive<F: AsFd>(
+    fd: &F,
+    idle: std::time::Duration,
+    interval: std::time::Duration,
+    count: u32,
+) -> Result<()> {
+    let secs = |d: std::time::Duration| {
+        u32::try_from(d.as_secs()).map_err(|_| Errno::EINVAL)
+    };
+    let idle = secs(idle)?;
+    let interval = secs(interval)?;
+
+    setsockopt(fd, sockopt::KeepAlive, &true)?;
+    #[cfg(apple_targets)]
+    setsockopt(fd, sockopt::TcpKeepAlive, &idle)?;
+    #[cfg(not(apple_targets))]
+    setsockopt(fd, sockopt::TcpKeepIdle, &idle)?;
+    setsockopt(fd, sockopt::TcpKeepInterval, &interval)?;
+    setsockopt(fd, sockopt::TcpKeepCount, &count)
+}
+
 /// Get the address of the peer connected to the socket `fd`.
 ///
 /// [Further reading](https://pubs.opengroup.org/onlinepubs/9699919799/functions/getpeername.html)
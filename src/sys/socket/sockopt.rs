@@ -1,4 +1,6 @@
 //! Socket options as used by `setsockopt` and `getsockopt`.
+#[cfg(target_os = "linux")]
+use super::GetSockOpt;
 #[cfg(linux_android)]
 use super::SetSockOpt;
 use crate::sys::time::TimeVal;
@@ -11,6 +13,8 @@ use std::ffi::CString;
 use std::ffi::{CStr, OsStr, OsString};
 use std::mem::{self, MaybeUninit};
 use std::os::unix::ffi::OsStrExt;
+#[cfg(target_os = "linux")]
+use std::os::unix::ffi::OsStringExt;
 #[cfg(linux_android)]
 use std::os::unix::io::{AsFd, AsRawFd};
 
@@ -302,6 +306,15 @@ sockopt_impl!(
 sockopt_impl!(
     /// Permits multiple AF_INET or AF_INET6 sockets to be bound to an
     /// identical socket address.
+    ///
+    /// Every socket of such a group must set this option before `bind`.  On
+    /// Linux, the sockets must also belong to the same effective user, and
+    /// incoming connections (for TCP) or datagrams (for UDP) are spread
+    /// evenly across the group.  Other systems don't balance the load, and
+    /// deliver to a single socket: on FreeBSD, use `ReusePortLb` for that.
+    ///
+    /// Reading the option returns the state kept by the kernel, so it can be
+    /// used to check that setting it took effect.
     ReusePort,
     Both,
     libc::SOL_SOCKET,
@@ -537,6 +550,9 @@ sockopt_impl!(
     #[cfg_attr(docsrs, doc(cfg(feature = "net")))]
     /// If enabled, this boolean option allows binding to an IP address that
     /// is nonlocal or does not (yet) exist.
+    ///
+    /// Without it, such a `bind` fails with `EADDRNOTAVAIL`.  Unlike
+    /// [`IpTransparent`], it requires no privileges.
     IpFreebind,
     Both,
     libc::IPPROTO_IP,
@@ -584,6 +600,11 @@ sockopt_impl!(
 sockopt_impl!(
     /// If this option is enabled, out-of-band data is directly placed into
     /// the receive data stream.
+    ///
+    /// Otherwise, the urgent byte of a TCP stream is kept apart from the
+    /// normal data and has to be read with
+    /// [`MSG_OOB`](super::MsgFlags::MSG_OOB).  Either way, a normal read
+    /// stops at the urgent data mark.
     OobInline,
     Both,
     libc::SOL_SOCKET,
@@ -614,6 +635,20 @@ sockopt_impl!(
     libc::SO_KEEPALIVE,
     bool
 );
+#[cfg(any(apple_targets, freebsdlike, target_os = "netbsd"))]
+sockopt_impl!(
+    /// Do not generate `SIGPIPE` when writing to a socket whose peer has
+    /// closed the connection.  The write fails with `EPIPE` instead.
+    ///
+    /// This is the per-socket counterpart of
+    /// [`MSG_NOSIGNAL`](super::MsgFlags::MSG_NOSIGNAL), and the only option
+    /// on platforms, like macOS, that lack that flag.
+    NoSigPipe,
+    Both,
+    libc::SOL_SOCKET,
+    libc::SO_NOSIGPIPE,
+    bool
+);
 #[cfg(freebsdlike)]
 sockopt_impl!(
     /// Get the credentials of the peer process of a connected unix domain
@@ -782,6 +817,24 @@ sockopt_impl!(
     libc::TCP_FASTOPEN_CONNECT,
     bool
 );
+#[cfg(linux_android)]
+#[cfg(feature = "net")]
+sockopt_impl!(
+    #[cfg_attr(docsrs, doc(cfg(feature = "net")))]
+    /// Enables TCP Fast Open (RFC 7413) on a listening socket. The value is
+    /// the maximum length of the queue of pending Fast Open requests, those
+    /// whose three-way handshake has not completed yet.
+    ///
+    /// The server side of Fast Open also has to be enabled system-wide, by
+    /// setting bit `0x2` of the `net.ipv4.tcp_fastopen` sysctl. Clients need
+    /// bit `0x1`, which is set by default. See
+    /// [tcp(7)](https://man7.org/linux/man-pages/man7/tcp.7.html).
+    TcpFastOpen,
+    Both,
+    libc::IPPROTO_TCP,
+    libc::TCP_FASTOPEN,
+    u32
+);
 sockopt_impl!(
     /// Sets or gets the maximum socket receive buffer in bytes.
     RcvBuf,
@@ -917,6 +970,10 @@ sockopt_impl!(
 sockopt_impl!(
     #[cfg_attr(docsrs, doc(cfg(feature = "net")))]
     /// Setting this boolean option enables transparent proxying on this socket.
+    ///
+    /// This lets the socket bind to non-local addresses, and receive
+    /// connections redirected to it by `TPROXY` rules.  Setting it requires
+    /// `CAP_NET_ADMIN` or `CAP_NET_RAW`, and fails with `EPERM` otherwise.
     IpTransparent,
     Both,
     libc::SOL_IP,
@@ -1376,6 +1433,110 @@ where
     }
 }
 
+/// Attach a classic BPF program to the socket, to filter the packets it
+/// receives.
+///
+/// The value is any contiguous sequence of `libc::sock_filter` instructions,
+/// such as a slice or a `Vec`.  A program of more than `u16::MAX`
+/// instructions is rejected with `EINVAL`.  Attaching a program replaces any
+/// previous one, unless it was locked with [`LockFilter`].
+///
+/// ```
+/// # use nix::sys::socket::*;
+/// # use nix::sys::socket::sockopt::AttachFilter;
+/// // Accept every packet, whole.
+/// let accept_all = [libc::sock_filter {
+///     code: (libc::BPF_RET | libc::BPF_K) as u16,
+///     jt: 0,
+///     jf: 0,
+///     k: u32::MAX,
+/// }];
+/// let fd = socket(AddressFamily::Inet, SockType::Datagram, SockFlag::empty(),
+///     None).unwrap();
+/// setsockopt(&fd, AttachFilter::default(), &accept_all).unwrap();
+/// ```
+///
+/// See also [socket(7)](https://man7.org/linux/man-pages/man7/socket.7.html)
+/// and [filter](https://docs.kernel.org/networking/filter.html).
+#[cfg(target_os = "linux")]
+#[derive(Clone, Debug)]
+pub struct AttachFilter<T>(::std::marker::PhantomData<T>);
+
+#[cfg(target_os = "linux")]
+impl<T> Default for AttachFilter<T> {
+    fn default() -> Self {
+        AttachFilter(Default::default())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl<T> SetSockOpt for AttachFilter<T>
+where
+    T: AsRef<[libc::sock_filter]> + Clone,
+{
+    type Val = T;
+
+    fn set<F: AsFd>(&self, fd: &F, val: &Self::Val) -> Result<()> {
+        let filter = val.as_ref();
+        let prog = libc::sock_fprog {
+            len: u16::try_from(filter.len()).map_err(|_| Errno::EINVAL)?,
+            // The kernel only reads the program
+            filter: filter.as_ptr().cast_mut(),
+        };
+        unsafe {
+            let res = libc::setsockopt(
+                fd.as_fd().as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_ATTACH_FILTER,
+                &prog as *const libc::sock_fprog as *const c_void,
+                mem::size_of::<libc::sock_fprog>() as libc::socklen_t,
+            );
+            Errno::result(res).map(drop)
+        }
+    }
+}
+
+/// Remove the classic BPF program attached with [`AttachFilter`].
+///
+/// Fails with `ENOENT` if no program is attached, and with `EPERM` if it was
+/// locked with [`LockFilter`].
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DetachFilter;
+
+#[cfg(target_os = "linux")]
+impl SetSockOpt for DetachFilter {
+    type Val = ();
+
+    fn set<F: AsFd>(&self, fd: &F, _val: &()) -> Result<()> {
+        // The value is ignored, but the kernel checks its length
+        let unused: c_int = 0;
+        unsafe {
+            let res = libc::setsockopt(
+                fd.as_fd().as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_DETACH_FILTER,
+                &unused as *const c_int as *const c_void,
+                mem::size_of::<c_int>() as libc::socklen_t,
+            );
+            Errno::result(res).map(drop)
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+sockopt_impl!(
+    /// Prevent the socket's filter from being changed or detached.
+    ///
+    /// Once set, [`AttachFilter`] and [`DetachFilter`] fail with `EPERM`, and
+    /// this option can't be cleared again.
+    LockFilter,
+    Both,
+    libc::SOL_SOCKET,
+    libc::SO_LOCK_FILTER,
+    bool
+);
+
 /// Value used with the [`TcpTlsTx`] and [`TcpTlsRx`] socket options.
 #[cfg(target_os = "linux")]
 #[derive(Copy, Clone, Debug)]
@@ -1482,6 +1643,56 @@ impl SetSockOpt for TcpTlsRx {
     }
 }
 
+/// Return the security context of the peer of a Unix domain socket, as set by
+/// the active Linux Security Module (e.g. an SELinux context or an AppArmor
+/// profile name such as `unconfined`).
+///
+/// The label's length isn't known up front, so the buffer is grown until the
+/// whole label fits.  Any trailing NUL byte is stripped.  Fails with
+/// `ENOPROTOOPT` if no security module provides peer labels.
+///
+/// # See Also
+/// [unix(7)](https://man7.org/linux/man-pages/man7/unix.7.html)
+#[cfg(target_os = "linux")]
+#[derive(Copy, Clone, Debug)]
+pub struct PeerSec;
+
+#[cfg(target_os = "linux")]
+impl GetSockOpt for PeerSec {
+    type Val = OsString;
+
+    fn get<F: AsFd>(&self, fd: &F) -> Result<OsString> {
+        let mut buf: Vec<u8> = Vec::with_capacity(256);
+        loop {
+            let mut len = buf.capacity() as socklen_t;
+            let res = unsafe {
+                libc::getsockopt(
+                    fd.as_fd().as_raw_fd(),
+                    libc::SOL_SOCKET,
+                    libc::SO_PEERSEC,
+                    buf.as_mut_ptr().cast(),
+                    &mut len,
+                )
+            };
+            match Errno::result(res) {
+                Ok(_) => {
+                    unsafe { buf.set_len(len as usize) };
+                    if buf.last() == Some(&0) {
+                        buf.pop();
+                    }
+                    return Ok(OsString::from_vec(buf));
+                }
+                // On ERANGE the kernel stores the required length in `len`
+                Err(Errno::ERANGE) => {
+                    let wanted = (len as usize).max(buf.capacity() * 2);
+                    buf.reserve(wanted);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
 /*
  *
  * ===== Accessor helpers =====
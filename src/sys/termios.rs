@@ -831,6 +831,180 @@ pub fn cfmakesane(termios: &mut Termios) {
     termios.update_wrapper();
 }
 
+/// Parity checking of a serial line, for [`TermiosBuilder::parity`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Parity {
+    /// No parity bit.
+    None,
+    /// An even parity bit.
+    Even,
+    /// An odd parity bit.
+    Odd,
+}
+
+/// Flow control of a serial line, for [`TermiosBuilder::flow_control`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FlowControl {
+    /// No flow control.
+    None,
+    /// XON/XOFF software flow control.
+    Software,
+    /// RTS/CTS hardware flow control.
+    #[cfg(not(any(target_os = "redox", target_os = "aix")))]
+    Hardware,
+}
+
+/// Builder for the [`Termios`] of a serial port.
+///
+/// Starting from the current configuration of the port, this takes care of
+/// the `c_cflag` and `c_iflag` bits for the usual serial line settings.
+/// Settings that aren't given are left as they are, except that the receiver
+/// is always enabled and modem control lines are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use nix::sys::termios::{tcgetattr, tcsetattr, BaudRate, Parity, SetArg, TermiosBuilder};
+/// # fn main() -> nix::Result<()> {
+/// # let port = std::fs::File::open("/dev/ttyS0").unwrap();
+/// let termios = TermiosBuilder::new(tcgetattr(&port)?)
+///     .baud(BaudRate::B115200)
+///     .data_bits(8)
+///     .parity(Parity::None)
+///     .stop_bits(1)
+///     .raw()
+///     .build()?;
+/// tcsetattr(&port, SetArg::TCSANOW, &termios)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TermiosBuilder {
+    termios: Termios,
+    baud: Option<BaudRate>,
+    data_bits: Option<u8>,
+    parity: Option<Parity>,
+    stop_bits: Option<u8>,
+    flow_control: Option<FlowControl>,
+    raw: bool,
+}
+
+impl TermiosBuilder {
+    /// Start from `termios`, usually obtained with [`tcgetattr`].
+    pub fn new(termios: Termios) -> Self {
+        TermiosBuilder {
+            termios,
+            baud: None,
+            data_bits: None,
+            parity: None,
+            stop_bits: None,
+            flow_control: None,
+            raw: false,
+        }
+    }
+
+    /// Set both the input and output baud rates.
+    pub fn baud(mut self, baud: BaudRate) -> Self {
+        self.baud = Some(baud);
+        self
+    }
+
+    /// Set the number of data bits per character, from 5 to 8.
+    pub fn data_bits(mut self, bits: u8) -> Self {
+        self.data_bits = Some(bits);
+        self
+    }
+
+    /// Set the parity checking.
+    pub fn parity(mut self, parity: Parity) -> Self {
+        self.parity = Some(parity);
+        self
+    }
+
+    /// Set the number of stop bits, 1 or 2.
+    pub fn stop_bits(mut self, bits: u8) -> Self {
+        self.stop_bits = Some(bits);
+        self
+    }
+
+    /// Set the flow control.
+    pub fn flow_control(mut self, flow_control: FlowControl) -> Self {
+        self.flow_control = Some(flow_control);
+        self
+    }
+
+    /// Put the port in raw mode with [`cfmakeraw`], before applying the
+    /// other settings.
+    pub fn raw(mut self) -> Self {
+        self.raw = true;
+        self
+    }
+
+    /// Build the `Termios`, to be applied with [`tcsetattr`].
+    ///
+    /// Fails with `EINVAL` if the number of data or stop bits is not
+    /// supported.
+    pub fn build(self) -> Result<Termios> {
+        let mut termios = self.termios;
+
+        if self.raw {
+            cfmakeraw(&mut termios);
+        }
+        termios.control_flags |= ControlFlags::CREAD | ControlFlags::CLOCAL;
+
+        if let Some(bits) = self.data_bits {
+            let size = match bits {
+                5 => ControlFlags::CS5,
+                6 => ControlFlags::CS6,
+                7 => ControlFlags::CS7,
+                8 => ControlFlags::CS8,
+                _ => return Err(Errno::EINVAL),
+            };
+            termios.control_flags.remove(ControlFlags::CSIZE);
+            termios.control_flags.insert(size);
+        }
+        if let Some(parity) = self.parity {
+            let flags = &mut termios.control_flags;
+            match parity {
+                Parity::None => flags.remove(ControlFlags::PARENB),
+                Parity::Even => {
+                    flags.insert(ControlFlags::PARENB);
+                    flags.remove(ControlFlags::PARODD);
+                }
+                Parity::Odd => {
+                    flags.insert(ControlFlags::PARENB | ControlFlags::PARODD)
+                }
+            }
+            termios
+                .input_flags
+                .set(InputFlags::INPCK, parity != Parity::None);
+        }
+        match self.stop_bits {
+            None => (),
+            Some(1) => termios.control_flags.remove(ControlFlags::CSTOPB),
+            Some(2) => termios.control_flags.insert(ControlFlags::CSTOPB),
+            Some(_) => return Err(Errno::EINVAL),
+        }
+        if let Some(flow_control) = self.flow_control {
+            let software = InputFlags::IXON | InputFlags::IXOFF;
+            termios
+                .input_flags
+                .set(software, flow_control == FlowControl::Software);
+            #[cfg(not(any(target_os = "redox", target_os = "aix")))]
+            termios.control_flags.set(
+                ControlFlags::CRTSCTS,
+                flow_control == FlowControl::Hardware,
+            );
+        }
+        if let Some(baud) = self.baud {
+            cfsetispeed(&mut termios, baud)?;
+            cfsetospeed(&mut termios, baud)?;
+        }
+
+        Ok(termios)
+    }
+}
+
 /// Return the configuration of a port
 /// [tcgetattr(3p)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/tcgetattr.html)).
 ///
@@ -137,6 +137,11 @@ pub(crate) mod timer {
     }
 }
 
+/// Constructors and accessors shared by [`TimeSpec`] and [`TimeVal`].
+///
+/// The constructors normalize their argument, carrying whole seconds out of
+/// the sub-second field so that it always lies in `[0, 1s)`.  For example,
+/// `TimeSpec::milliseconds(1500)` is 1 second and 500,000,000 nanoseconds.
 pub trait TimeValLike: Sized {
     #[inline]
     fn zero() -> Self {
@@ -328,6 +333,9 @@ impl TimeValLike for TimeSpec {
 }
 
 impl TimeSpec {
+    /// A `TimeSpec` of zero length, as used for a nonblocking poll.
+    pub const ZERO: TimeSpec = TimeSpec::new(0, 0);
+
     /// Leave the timestamp unchanged.
     #[cfg(not(target_os = "redox"))]
     // At the time of writing this PR, redox does not support this feature
@@ -377,6 +385,66 @@ impl TimeSpec {
     pub const fn from_timespec(timespec: timespec) -> Self {
         Self(timespec)
     }
+
+    /// Convert to a [`Duration`], or `Duration::ZERO` if `self` is negative.
+    // The cast is not unnecessary on all platforms.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn to_duration(&self) -> Duration {
+        if self.tv_sec() < 0 {
+            Duration::ZERO
+        } else {
+            Duration::new(self.tv_sec() as u64, self.tv_nsec() as u32)
+        }
+    }
+
+    /// Add two `TimeSpec`s, saturating at the largest or smallest
+    /// representable value instead of overflowing.
+    pub fn saturating_add(self, other: TimeSpec) -> TimeSpec {
+        TimeSpec::saturating_from_nanos(
+            self.total_nanos() + other.total_nanos(),
+        )
+    }
+
+    /// Subtract `other` from `self`, saturating at zero instead of producing
+    /// a negative value.
+    pub fn saturating_sub(self, other: TimeSpec) -> TimeSpec {
+        let nanos = self.total_nanos() - other.total_nanos();
+        TimeSpec::saturating_from_nanos(nanos.max(0))
+    }
+
+    /// The time elapsed from `earlier` to `self`, or zero if `earlier` is
+    /// later than `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use nix::time::ClockId;
+    /// let start = ClockId::CLOCK_MONOTONIC.now().unwrap();
+    /// // ...
+    /// let end = ClockId::CLOCK_MONOTONIC.now().unwrap();
+    /// println!("took {:?}", end.elapsed_since(&start));
+    /// ```
+    pub fn elapsed_since(&self, earlier: &TimeSpec) -> Duration {
+        self.saturating_sub(*earlier).to_duration()
+    }
+
+    fn total_nanos(&self) -> i128 {
+        i128::from(self.tv_sec()) * i128::from(NANOS_PER_SEC)
+            + i128::from(self.tv_nsec())
+    }
+
+    fn saturating_from_nanos(nanos: i128) -> TimeSpec {
+        let nanos_per_sec = i128::from(NANOS_PER_SEC);
+        let max =
+            i128::from(TS_MAX_SECONDS) * nanos_per_sec + nanos_per_sec - 1;
+        let min = i128::from(TS_MIN_SECONDS) * nanos_per_sec;
+        let nanos = nanos.clamp(min, max);
+        // Euclidean division keeps tv_nsec within [0, NANOS_PER_SEC)
+        TimeSpec::new(
+            nanos.div_euclid(nanos_per_sec) as time_t,
+            nanos.rem_euclid(nanos_per_sec) as timespec_tv_nsec_t,
+        )
+    }
 }
 
 impl ops::Neg for TimeSpec {
@@ -585,6 +653,9 @@ impl TimeValLike for TimeVal {
 }
 
 impl TimeVal {
+    /// A `TimeVal` of zero length, as used for a nonblocking poll.
+    pub const ZERO: TimeVal = TimeVal::new(0, 0);
+
     /// Construct a new `TimeVal` from its components
     #[cfg_attr(target_env = "musl", allow(deprecated))] // https://github.com/rust-lang/libc/issues/1848
     pub const fn new(seconds: time_t, microseconds: suseconds_t) -> Self {
@@ -610,6 +681,39 @@ impl TimeVal {
     pub const fn tv_usec(&self) -> suseconds_t {
         self.0.tv_usec
     }
+
+    /// Add two `TimeVal`s, saturating at the largest or smallest
+    /// representable value instead of overflowing.
+    pub fn saturating_add(self, other: TimeVal) -> TimeVal {
+        TimeVal::saturating_from_micros(
+            self.total_micros() + other.total_micros(),
+        )
+    }
+
+    /// Subtract `other` from `self`, saturating at zero instead of producing
+    /// a negative value.
+    pub fn saturating_sub(self, other: TimeVal) -> TimeVal {
+        let micros = self.total_micros() - other.total_micros();
+        TimeVal::saturating_from_micros(micros.max(0))
+    }
+
+    fn total_micros(&self) -> i128 {
+        i128::from(self.tv_sec()) * i128::from(MICROS_PER_SEC)
+            + i128::from(self.tv_usec())
+    }
+
+    fn saturating_from_micros(micros: i128) -> TimeVal {
+        let micros_per_sec = i128::from(MICROS_PER_SEC);
+        let max =
+            i128::from(TV_MAX_SECONDS) * micros_per_sec + micros_per_sec - 1;
+        let min = i128::from(TV_MIN_SECONDS) * micros_per_sec;
+        let micros = micros.clamp(min, max);
+        // Euclidean division keeps tv_usec within [0, MICROS_PER_SEC)
+        TimeVal::new(
+            micros.div_euclid(micros_per_sec) as time_t,
+            micros.rem_euclid(micros_per_sec) as suseconds_t,
+        )
+    }
 }
 
 impl ops::Neg for TimeVal {
@@ -33,7 +33,9 @@ pub use crate::sys::time::timer::{Expiration, TimerSetTimeFlags};
 use crate::unistd::read;
 use crate::{errno::Errno, Result};
 use libc::c_int;
-use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::io::{
+    AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd,
+};
 
 /// A timerfd instance. This is also a file descriptor, you can feed it to
 /// other interfaces taking file descriptors as arguments, [`epoll`] for example.
@@ -50,6 +52,18 @@ impl AsFd for TimerFd {
     }
 }
 
+impl AsRawFd for TimerFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for TimerFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.fd.into_raw_fd()
+    }
+}
+
 impl FromRawFd for TimerFd {
     unsafe fn from_raw_fd(fd: RawFd) -> Self {
         TimerFd {
@@ -81,8 +95,12 @@ libc_enum! {
         /// that the system was suspended.
         CLOCK_BOOTTIME,
         /// Like `CLOCK_REALTIME`, but will wake the system if it is suspended.
+        ///
+        /// Requires the `CAP_WAKE_ALARM` capability.
         CLOCK_REALTIME_ALARM,
         /// Like `CLOCK_BOOTTIME`, but will wake the system if it is suspended.
+        ///
+        /// Requires the `CAP_WAKE_ALARM` capability.
         CLOCK_BOOTTIME_ALARM,
     }
 }
@@ -102,6 +120,10 @@ impl TimerFd {
     /// Creates a new timer based on the clock defined by `clockid`. The
     /// underlying fd can be assigned specific flags with `flags` (CLOEXEC,
     /// NONBLOCK). The underlying fd will be closed on drop.
+    ///
+    /// Timers on `CLOCK_REALTIME_ALARM` and `CLOCK_BOOTTIME_ALARM` wake the
+    /// system from suspend when they expire.  Creating one requires the
+    /// `CAP_WAKE_ALARM` capability; without it this fails with `EPERM`.
     #[doc(alias("timerfd_create"))]
     pub fn new(clockid: ClockId, flags: TimerFlags) -> Result<Self> {
         Errno::result(unsafe {
@@ -213,6 +235,9 @@ impl TimerFd {
     /// Wait for the configured alarm to expire.
     ///
     /// Note: If the alarm is unset, then you will wait forever.
+    ///
+    /// If the timer was created with `TFD_NONBLOCK` and has not expired yet,
+    /// this returns `Err(Errno::EAGAIN)` immediately instead of blocking.
     pub fn wait(&self) -> Result<()> {
         while let Err(e) = read(&self.fd, &mut [0u8; 8]) {
             if e == Errno::ECANCELED {
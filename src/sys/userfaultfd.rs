@@ -0,0 +1,35 @@
+//! Handle page faults in user space.
+//!
+//! A `userfaultfd` file descriptor lets a thread resolve the page faults
+//! that other threads take on registered memory ranges.
+//!
+//! Creating one may require `CAP_SYS_PTRACE`, depending on the
+//! `vm.unprivileged_userfaultfd` sysctl.
+//!
+//! For more documentation, please read
+//! [userfaultfd(2)](https://man7.org/linux/man-pages/man2/userfaultfd.2.html).
+use crate::errno::Errno;
+use crate::Result;
+use libc::c_int;
+use std::os::unix::io::{FromRawFd, OwnedFd};
+
+libc_bitflags! {
+    /// Flags for [`userfaultfd`].
+    pub struct UffdFlags: c_int {
+        /// Set the close-on-exec flag on the new file descriptor.
+        O_CLOEXEC;
+        /// Make reads from the new file descriptor non-blocking.
+        O_NONBLOCK;
+    }
+}
+
+/// Create a new userfaultfd.
+///
+/// # See Also
+/// [userfaultfd(2)](https://man7.org/linux/man-pages/man2/userfaultfd.2.html)
+pub fn userfaultfd(flags: UffdFlags) -> Result<OwnedFd> {
+    let res = unsafe { libc::syscall(libc::SYS_userfaultfd, flags.bits()) };
+
+    // Safe because the syscall returned a new file descriptor
+    Errno::result(res).map(|fd| unsafe { OwnedFd::from_raw_fd(fd as c_int) })
+}
@@ -0,0 +1,393 @@
+//! Get, set, list and remove extended attributes on files.
+//!
+//! Extended attributes are name-value pairs associated with inodes.  On Linux
+//! the name must carry a namespace prefix, such as `user.` or `trusted.`.
+//!
+//! Each operation comes in three flavors: the plain one follows symbolic
+//! links, the `l`-prefixed one operates on a symbolic link itself, and the
+//! `f`-prefixed one operates on an open file descriptor.
+//!
+//! # See Also
+//! * [`xattr(7)`](https://man7.org/linux/man-pages/man7/xattr.7.html)
+use crate::errno::Errno;
+use crate::{NixPath, Result};
+use libc::{c_char, c_int, c_void, size_t, ssize_t};
+use std::ffi::{CStr, OsString};
+use std::os::unix::ffi::OsStringExt;
+use std::os::unix::io::{AsFd, AsRawFd};
+
+libc_bitflags!(
+    /// Flags for [`setxattr`] and friends.
+    pub struct XattrFlags: c_int {
+        /// Fail with `EEXIST` if the attribute already exists.
+        XATTR_CREATE;
+        /// Fail with `ENODATA` (`ENOATTR` on Apple platforms) if the
+        /// attribute does not already exist.
+        XATTR_REPLACE;
+    }
+);
+
+#[cfg(apple_targets)]
+const NOFOLLOW: c_int = libc::XATTR_NOFOLLOW;
+
+/// Call `f` with a buffer of increasing size until the whole value fits.
+///
+/// `f` is first called with an empty buffer to learn the required size.
+/// Because the value may grow between calls, `ERANGE` causes a retry.
+fn read_to_vec<F>(mut f: F) -> Result<Vec<u8>>
+where
+    F: FnMut(*mut c_void, size_t) -> ssize_t,
+{
+    loop {
+        let size = Errno::result(f(std::ptr::null_mut(), 0))? as usize;
+        if size == 0 {
+            return Ok(Vec::new());
+        }
+        let mut buf = Vec::<u8>::with_capacity(size);
+        match Errno::result(f(buf.as_mut_ptr().cast(), buf.capacity())) {
+            Ok(len) => {
+                // SAFETY: the kernel initialized `len` bytes of `buf`
+                unsafe { buf.set_len(len as usize) };
+                return Ok(buf);
+            }
+            Err(Errno::ERANGE) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Split a NUL-separated list of attribute names, as returned by `listxattr`.
+fn split_names(buf: Vec<u8>) -> Vec<OsString> {
+    buf.split(|&b| b == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| OsString::from_vec(name.to_vec()))
+        .collect()
+}
+
+fn getxattr_inner(
+    path: &CStr,
+    name: &CStr,
+    follow: bool,
+) -> Result<Vec<u8>> {
+    read_to_vec(|value, size| unsafe {
+        cfg_if::cfg_if! {
+            if #[cfg(apple_targets)] {
+                let options = if follow { 0 } else { NOFOLLOW };
+                libc::getxattr(
+                    path.as_ptr(),
+                    name.as_ptr(),
+                    value,
+                    size,
+                    0,
+                    options,
+                )
+            } else {
+                if follow {
+                    libc::getxattr(path.as_ptr(), name.as_ptr(), value, size)
+                } else {
+                    libc::lgetxattr(path.as_ptr(), name.as_ptr(), value, size)
+                }
+            }
+        }
+    })
+}
+
+/// Retrieve the value of the extended attribute `name` of the file at `path`.
+///
+/// # See Also
+/// * [`getxattr(2)`](https://man7.org/linux/man-pages/man2/getxattr.2.html)
+pub fn getxattr<P1, P2>(path: &P1, name: &P2) -> Result<Vec<u8>>
+where
+    P1: ?Sized + NixPath,
+    P2: ?Sized + NixPath,
+{
+    path.with_nix_path(|path| {
+        name.with_nix_path(|name| getxattr_inner(path, name, true))
+    })??
+}
+
+/// Like [`getxattr`], but does not follow a symbolic link at `path`.
+///
+/// # See Also
+/// * [`lgetxattr(2)`](https://man7.org/linux/man-pages/man2/getxattr.2.html)
+pub fn lgetxattr<P1, P2>(path: &P1, name: &P2) -> Result<Vec<u8>>
+where
+    P1: ?Sized + NixPath,
+    P2: ?Sized + NixPath,
+{
+    path.with_nix_path(|path| {
+        name.with_nix_path(|name| getxattr_inner(path, name, false))
+    })??
+}
+
+/// Like [`getxattr`], but operates on an open file descriptor.
+///
+/// # See Also
+/// * [`fgetxattr(2)`](https://man7.org/linux/man-pages/man2/getxattr.2.html)
+pub fn fgetxattr<Fd: AsFd, P: ?Sized + NixPath>(
+    fd: Fd,
+    name: &P,
+) -> Result<Vec<u8>> {
+    let fd = fd.as_fd().as_raw_fd();
+    name.with_nix_path(|name| {
+        read_to_vec(|value, size| unsafe {
+            cfg_if::cfg_if! {
+                if #[cfg(apple_targets)] {
+                    libc::fgetxattr(fd, name.as_ptr(), value, size, 0, 0)
+                } else {
+                    libc::fgetxattr(fd, name.as_ptr(), value, size)
+                }
+            }
+        })
+    })?
+}
+
+fn setxattr_inner(
+    path: &CStr,
+    name: &CStr,
+    value: &[u8],
+    flags: XattrFlags,
+    follow: bool,
+) -> Result<()> {
+    let res = unsafe {
+        cfg_if::cfg_if! {
+            if #[cfg(apple_targets)] {
+                let options = if follow {
+                    flags.bits()
+                } else {
+                    flags.bits() | NOFOLLOW
+                };
+                libc::setxattr(
+                    path.as_ptr(),
+                    name.as_ptr(),
+                    value.as_ptr().cast(),
+                    value.len(),
+                    0,
+                    options,
+                )
+            } else {
+                if follow {
+                    libc::setxattr(
+                        path.as_ptr(),
+                        name.as_ptr(),
+                        value.as_ptr().cast(),
+                        value.len(),
+                        flags.bits(),
+                    )
+                } else {
+                    libc::lsetxattr(
+                        path.as_ptr(),
+                        name.as_ptr(),
+                        value.as_ptr().cast(),
+                        value.len(),
+                        flags.bits(),
+                    )
+                }
+            }
+        }
+    };
+    Errno::result(res).map(drop)
+}
+
+/// Set the value of the extended attribute `name` of the file at `path`.
+///
+/// By default the attribute is created if it does not exist and replaced if
+/// it does.  Pass [`XattrFlags::XATTR_CREATE`] or [`XattrFlags::XATTR_REPLACE`]
+/// to require one or the other.
+///
+/// # See Also
+/// * [`setxattr(2)`](https://man7.org/linux/man-pages/man2/setxattr.2.html)
+pub fn setxattr<P1, P2>(
+    path: &P1,
+    name: &P2,
+    value: &[u8],
+    flags: XattrFlags,
+) -> Result<()>
+where
+    P1: ?Sized + NixPath,
+    P2: ?Sized + NixPath,
+{
+    path.with_nix_path(|path| {
+        name.with_nix_path(|name| {
+            setxattr_inner(path, name, value, flags, true)
+        })
+    })??
+}
+
+/// Like [`setxattr`], but does not follow a symbolic link at `path`.
+///
+/// # See Also
+/// * [`lsetxattr(2)`](https://man7.org/linux/man-pages/man2/setxattr.2.html)
+pub fn lsetxattr<P1, P2>(
+    path: &P1,
+    name: &P2,
+    value: &[u8],
+    flags: XattrFlags,
+) -> Result<()>
+where
+    P1: ?Sized + NixPath,
+    P2: ?Sized + NixPath,
+{
+    path.with_nix_path(|path| {
+        name.with_nix_path(|name| {
+            setxattr_inner(path, name, value, flags, false)
+        })
+    })??
+}
+
+/// Like [`setxattr`], but operates on an open file descriptor.
+///
+/// # See Also
+/// * [`fsetxattr(2)`](https://man7.org/linux/man-pages/man2/setxattr.2.html)
+pub fn fsetxattr<Fd: AsFd, P: ?Sized + NixPath>(
+    fd: Fd,
+    name: &P,
+    value: &[u8],
+    flags: XattrFlags,
+) -> Result<()> {
+    let fd = fd.as_fd().as_raw_fd();
+    let res = name.with_nix_path(|name| unsafe {
+        cfg_if::cfg_if! {
+            if #[cfg(apple_targets)] {
+                libc::fsetxattr(
+                    fd,
+                    name.as_ptr(),
+                    value.as_ptr().cast(),
+                    value.len(),
+                    0,
+                    flags.bits(),
+                )
+            } else {
+                libc::fsetxattr(
+                    fd,
+                    name.as_ptr(),
+                    value.as_ptr().cast(),
+                    value.len(),
+                    flags.bits(),
+                )
+            }
+        }
+    })?;
+    Errno::result(res).map(drop)
+}
+
+fn listxattr_inner(path: &CStr, follow: bool) -> Result<Vec<OsString>> {
+    read_to_vec(|list, size| unsafe {
+        let list = list.cast::<c_char>();
+        cfg_if::cfg_if! {
+            if #[cfg(apple_targets)] {
+                let options = if follow { 0 } else { NOFOLLOW };
+                libc::listxattr(path.as_ptr(), list, size, options)
+            } else {
+                if follow {
+                    libc::listxattr(path.as_ptr(), list, size)
+                } else {
+                    libc::llistxattr(path.as_ptr(), list, size)
+                }
+            }
+        }
+    })
+    .map(split_names)
+}
+
+/// List the names of the extended attributes of the file at `path`.
+///
+/// # See Also
+/// * [`listxattr(2)`](https://man7.org/linux/man-pages/man2/listxattr.2.html)
+pub fn listxattr<P: ?Sized + NixPath>(path: &P) -> Result<Vec<OsString>> {
+    path.with_nix_path(|path| listxattr_inner(path, true))?
+}
+
+/// Like [`listxattr`], but does not follow a symbolic link at `path`.
+///
+/// # See Also
+/// * [`llistxattr(2)`](https://man7.org/linux/man-pages/man2/listxattr.2.html)
+pub fn llistxattr<P: ?Sized + NixPath>(path: &P) -> Result<Vec<OsString>> {
+    path.with_nix_path(|path| listxattr_inner(path, false))?
+}
+
+/// Like [`listxattr`], but operates on an open file descriptor.
+///
+/// # See Also
+/// * [`flistxattr(2)`](https://man7.org/linux/man-pages/man2/listxattr.2.html)
+pub fn flistxattr<Fd: AsFd>(fd: Fd) -> Result<Vec<OsString>> {
+    let fd = fd.as_fd().as_raw_fd();
+    read_to_vec(|list, size| unsafe {
+        let list = list.cast::<c_char>();
+        cfg_if::cfg_if! {
+            if #[cfg(apple_targets)] {
+                libc::flistxattr(fd, list, size, 0)
+            } else {
+                libc::flistxattr(fd, list, size)
+            }
+        }
+    })
+    .map(split_names)
+}
+
+fn removexattr_inner(path: &CStr, name: &CStr, follow: bool) -> Result<()> {
+    let res = unsafe {
+        cfg_if::cfg_if! {
+            if #[cfg(apple_targets)] {
+                let options = if follow { 0 } else { NOFOLLOW };
+                libc::removexattr(path.as_ptr(), name.as_ptr(), options)
+            } else {
+                if follow {
+                    libc::removexattr(path.as_ptr(), name.as_ptr())
+                } else {
+                    libc::lremovexattr(path.as_ptr(), name.as_ptr())
+                }
+            }
+        }
+    };
+    Errno::result(res).map(drop)
+}
+
+/// Remove the extended attribute `name` from the file at `path`.
+///
+/// # See Also
+/// * [`removexattr(2)`](https://man7.org/linux/man-pages/man2/removexattr.2.html)
+pub fn removexattr<P1, P2>(path: &P1, name: &P2) -> Result<()>
+where
+    P1: ?Sized + NixPath,
+    P2: ?Sized + NixPath,
+{
+    path.with_nix_path(|path| {
+        name.with_nix_path(|name| removexattr_inner(path, name, true))
+    })??
+}
+
+/// Like [`removexattr`], but does not follow a symbolic link at `path`.
+///
+/// # See Also
+/// * [`lremovexattr(2)`](https://man7.org/linux/man-pages/man2/removexattr.2.html)
+pub fn lremovexattr<P1, P2>(path: &P1, name: &P2) -> Result<()>
+where
+    P1: ?Sized + NixPath,
+    P2: ?Sized + NixPath,
+{
+    path.with_nix_path(|path| {
+        name.with_nix_path(|name| removexattr_inner(path, name, false))
+    })??
+}
+
+/// Like [`removexattr`], but operates on an open file descriptor.
+///
+/// # See Also
+/// * [`fremovexattr(2)`](https://man7.org/linux/man-pages/man2/removexattr.2.html)
+pub fn fremovexattr<Fd: AsFd, P: ?Sized + NixPath>(
+    fd: Fd,
+    name: &P,
+) -> Result<()> {
+    let fd = fd.as_fd().as_raw_fd();
+    let res = name.with_nix_path(|name| unsafe {
+        cfg_if::cfg_if! {
+            if #[cfg(apple_targets)] {
+                libc::fremovexattr(fd, name.as_ptr(), 0)
+            } else {
+                libc::fremovexattr(fd, name.as_ptr())
+            }
+        }
+    })?;
+    Errno::result(res).map(drop)
+}
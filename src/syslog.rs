@@ -1,8 +1,11 @@
 //! Interfaces for controlling system log.
 
+use crate::errno::Errno;
 use crate::{NixPath, Result};
-use std::ffi::OsStr;
+use std::ffi::{CString, OsStr};
+use std::os::unix::ffi::OsStrExt;
 use std::ptr;
+use std::sync::{Mutex, PoisonError};
 
 /// Logging options of subsequent [`syslog`] calls can be set by calling [`openlog`].
 ///
@@ -42,6 +45,10 @@ pub fn openlog(
 /// The parameter `ident` is a string that will be prepended to every message. The `logopt`
 /// argument specifies logging options. The `facility` parameter encodes a default facility to be
 /// assigned to all messages that do not have an explicit facility encoded.
+///
+/// Some libcs keep a pointer to `ident` rather than a copy, but this function
+/// only makes a temporary copy of it.  Use [`openlog_owned`] to be sure that
+/// `ident` outlives subsequent [`syslog`] calls.
 #[cfg(not(target_os = "linux"))]
 pub fn openlog<S: AsRef<OsStr> + ?Sized>(
     ident: Option<&S>,
@@ -62,6 +69,37 @@ pub fn openlog<S: AsRef<OsStr> + ?Sized>(
     Ok(())
 }
 
+/// Like [`openlog`], but takes ownership of `ident`.
+///
+/// libc may keep a pointer to `ident` for as long as the log is open, so
+/// `ident` is kept in a process-wide slot until the next call to this
+/// function replaces it, and is never freed while libc could still refer to
+/// it.  `None` makes libc use the program name.
+///
+/// # Examples
+///
+/// ```
+/// use nix::syslog::{openlog_owned, syslog, Facility, LogFlags, Severity};
+/// use std::ffi::CString;
+///
+/// let ident = CString::new(format!("worker-{}", 1)).unwrap();
+/// openlog_owned(Some(ident), LogFlags::LOG_PID, Facility::LOG_USER);
+/// syslog(Severity::LOG_INFO, "logged as worker-1").unwrap();
+/// ```
+pub fn openlog_owned(
+    ident: Option<CString>,
+    logopt: LogFlags,
+    facility: Facility,
+) {
+    static IDENT: Mutex<Option<CString>> = Mutex::new(None);
+
+    let mut slot = IDENT.lock().unwrap_or_else(PoisonError::into_inner);
+    let ptr = ident.as_ref().map_or(ptr::null(), |ident| ident.as_ptr());
+    unsafe { libc::openlog(ptr, logopt.bits(), facility as libc::c_int) };
+    // Only drop the previous ident once libc has stopped referring to it.
+    *slot = ident;
+}
+
 /// Writes message to the system message logger.
 ///
 /// The message is then written to the system console, log files, logged-in users, or forwarded
@@ -95,6 +133,118 @@ where
     Ok(())
 }
 
+/// Buffer that formats short messages on the stack, and only allocates for
+/// long ones.
+struct MessageBuf {
+    stack: [u8; 256],
+    len: usize,
+    heap: Vec<u8>,
+}
+
+impl MessageBuf {
+    fn new() -> Self {
+        MessageBuf {
+            stack: [0; 256],
+            len: 0,
+            heap: Vec::new(),
+        }
+    }
+
+    /// Append the NUL terminator, and return the whole C string.
+    fn finish(&mut self) -> &[u8] {
+        // Leave room for the terminator on the stack
+        if self.heap.is_empty() && self.len < self.stack.len() {
+            self.stack[self.len] = 0;
+            &self.stack[..=self.len]
+        } else {
+            if self.heap.is_empty() {
+                self.heap.extend_from_slice(&self.stack[..self.len]);
+            }
+            self.heap.push(0);
+            &self.heap
+        }
+    }
+}
+
+impl std::fmt::Write for MessageBuf {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let bytes = s.as_bytes();
+        if self.heap.is_empty() && self.len + bytes.len() <= self.stack.len() {
+            self.stack[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        } else {
+            if self.heap.is_empty() {
+                self.heap.reserve(self.len + bytes.len() + 1);
+                self.heap.extend_from_slice(&self.stack[..self.len]);
+            }
+            self.heap.extend_from_slice(bytes);
+        }
+        Ok(())
+    }
+}
+
+/// Writes a formatted message to the system message logger.
+///
+/// Messages of up to 255 bytes are formatted on the stack, without
+/// allocating.  If the formatted message contains a NUL byte, nothing is
+/// logged and `EINVAL` is returned.  See also the [`syslog!`](crate::syslog!)
+/// macro.
+///
+/// # Examples
+///
+/// ```rust
+/// use nix::syslog::{syslog_fmt, Severity};
+///
+/// let port = 22;
+/// syslog_fmt(Severity::LOG_INFO, format_args!("listening on {port}")).unwrap();
+/// ```
+pub fn syslog_fmt<P>(priority: P, args: std::fmt::Arguments) -> Result<()>
+where
+    P: Into<Priority>,
+{
+    use std::fmt::Write;
+
+    let priority = priority.into();
+    let mut buf = MessageBuf::new();
+    // MessageBuf never fails, so this only errors if a Display impl does.
+    buf.write_fmt(args).map_err(|_| Errno::EINVAL)?;
+    let message = buf.finish();
+    if message[..message.len() - 1].contains(&0) {
+        return Err(Errno::EINVAL);
+    }
+
+    unsafe {
+        libc::syslog(
+            priority.0,
+            b"%s\0".as_ptr().cast(),
+            message.as_ptr().cast::<libc::c_char>(),
+        )
+    };
+    Ok(())
+}
+
+/// Writes a message to the system message logger, formatting it like
+/// [`format!`].
+///
+/// This expands to a call to [`syslog_fmt`], and returns its result.
+///
+/// # Examples
+///
+/// ```rust
+/// use nix::syslog::Severity;
+///
+/// let addr = "192.0.2.1";
+/// let err = "connection reset";
+/// nix::syslog!(Severity::LOG_INFO, "connection from {} failed: {}", addr, err)
+///     .unwrap();
+/// ```
+#[macro_export]
+macro_rules! syslog {
+    ($priority:expr, $($arg:tt)+) => {
+        $crate::syslog::syslog_fmt($priority, ::std::format_args!($($arg)+))
+    };
+}
+
 /// Set the process-wide priority mask to `mask` and return the previous mask
 /// value.
 ///
@@ -117,8 +267,63 @@ pub fn closelog() {
     unsafe { libc::closelog() }
 }
 
+/// Like [`openlog`], but only until the returned guard is dropped, which
+/// calls [`closelog`].
+///
+/// After that, [`syslog`] behaves as if `openlog` had never been called, so
+/// a subsystem can log under its own `ident` without changing the
+/// configuration of the rest of the process for good.  Since libc keeps a
+/// pointer to `ident` rather than a copy, the guard owns a copy of it, that
+/// lives as long as the guard.
+///
+/// The syslog configuration is global, so only one guard should be alive at
+/// a time, and nothing else should call `openlog` or `closelog` meanwhile.
+///
+/// # Examples
+///
+/// ```
+/// use nix::syslog::{openlog_scoped, syslog, Facility, LogFlags, Severity};
+///
+/// let ident = Some("subsystem");
+/// {
+///     let _guard = openlog_scoped(ident, LogFlags::LOG_PID, Facility::LOG_USER)
+///         .unwrap();
+///     syslog(Severity::LOG_INFO, "logged as subsystem").unwrap();
+/// }
+/// syslog(Severity::LOG_INFO, "logged with the default ident").unwrap();
+/// ```
+pub fn openlog_scoped<S: AsRef<OsStr> + ?Sized>(
+    ident: Option<&S>,
+    logopt: LogFlags,
+    facility: Facility,
+) -> Result<SyslogGuard> {
+    let ident = ident
+        .map(|ident| CString::new(ident.as_ref().as_bytes()))
+        .transpose()
+        .map_err(|_| Errno::EINVAL)?;
+    let ptr = ident.as_ref().map_or(ptr::null(), |ident| ident.as_ptr());
+    unsafe { libc::openlog(ptr, logopt.bits(), facility as libc::c_int) };
+
+    Ok(SyslogGuard { _ident: ident })
+}
+
+/// Guard returned by [`openlog_scoped`], which calls [`closelog`] when
+/// dropped.
+#[derive(Debug)]
+#[must_use = "the syslog configuration is reset as soon as the guard is dropped"]
+pub struct SyslogGuard {
+    // libc refers to this until closelog is called
+    _ident: Option<CString>,
+}
+
+impl Drop for SyslogGuard {
+    fn drop(&mut self) {
+        closelog();
+    }
+}
+
 /// System log priority mask.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 pub struct LogMask(libc::c_int);
 
 impl LogMask {
@@ -136,12 +341,41 @@ impl LogMask {
         Self(1 << pri)
     }
 
+    /// Creates a mask for the specified priority only.
+    ///
+    /// Same as [`of_priority`](Self::of_priority).
+    #[doc(alias("LOG_MASK"))]
+    pub fn just(priority: Severity) -> Self {
+        Self::of_priority(priority)
+    }
+
     /// Returns if the mask for the specified `priority` is set.
     pub fn contains(&self, priority: Severity) -> bool {
         let priority = Self::of_priority(priority);
         let and_result = *self & priority;
         and_result.0 != 0
     }
+
+    /// Returns whether a message of the given `severity` would be logged
+    /// under this mask.
+    ///
+    /// Use it together with the current mask to skip formatting messages
+    /// that `syslog` would drop anyway.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use nix::syslog::{setlogmask, syslog, Severity};
+    ///
+    /// let mask = setlogmask(None);
+    /// if mask.allows(Severity::LOG_DEBUG) {
+    ///     let state = vec![1, 2, 3];
+    ///     syslog(Severity::LOG_DEBUG, &format!("state: {state:?}")).unwrap();
+    /// }
+    /// ```
+    pub fn allows(&self, severity: Severity) -> bool {
+        self.contains(severity)
+    }
 }
 
 impl std::ops::BitOr for LogMask {
@@ -273,6 +507,37 @@ libc_enum! {
         LOG_NEWS,
         /// The uucp system.
         LOG_UUCP,
+        /// The authorization system, for messages that may contain private
+        /// information and should only be readable by privileged users.
+        #[cfg(any(
+            linux_android,
+            bsd,
+            target_os = "hurd",
+            target_os = "aix"
+        ))]
+        LOG_AUTHPRIV,
+        /// The file transfer protocol daemons: ftpd(8), tftpd(8).
+        #[cfg(any(linux_android, bsd, target_os = "hurd"))]
+        LOG_FTP,
+        /// The cron daemon: cron(8).
+        #[cfg(any(
+            linux_android,
+            bsd,
+            solarish,
+            target_os = "hurd",
+            target_os = "aix"
+        ))]
+        LOG_CRON,
+        /// The network time protocol system.
+        #[cfg(freebsdlike)]
+        LOG_NTP,
+        /// Security subsystems, such as ipfw(4).
+        #[cfg(freebsdlike)]
+        LOG_SECURITY,
+        /// Messages written to /dev/console by the kernel console output
+        /// driver.
+        #[cfg(freebsdlike)]
+        LOG_CONSOLE,
         /// Reserved for local use.
         LOG_LOCAL0,
         /// Reserved for local use.
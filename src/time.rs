@@ -1,5 +1,7 @@
 //! Sleep, query system clocks, and set system clock
 use crate::sys::time::TimeSpec;
+#[cfg(any(target_os = "linux", bsd))]
+use crate::sys::time::TimeVal;
 #[cfg(any(freebsdlike, linux_android, target_os = "emscripten"))]
 #[cfg(feature = "process")]
 use crate::unistd::Pid;
@@ -19,6 +21,26 @@ impl ClockId {
         ClockId(clk_id)
     }
 
+    /// Returns the `ClockId` of the dynamic clock behind a clock device, such
+    /// as a PTP hardware clock opened from `/dev/ptp0`.
+    ///
+    /// This is the `FD_TO_CLOCKID` macro from the kernel documentation.  The
+    /// returned id is only usable with [`clock_gettime`], [`clock_getres`]
+    /// and [`clock_settime`] while `fd` stays open; for any other kind of file
+    /// these fail with `EINVAL`.
+    ///
+    /// # See Also
+    /// [Linux PTP hardware clock infrastructure](https://docs.kernel.org/driver-api/ptp.html)
+    #[cfg(target_os = "linux")]
+    pub fn from_fd<Fd: std::os::fd::AsFd>(fd: &Fd) -> Self {
+        use std::os::fd::AsRawFd;
+
+        // CLOCKFD from the kernel's posix-timers.h
+        const CLOCKFD: clockid_t = 3;
+        let fd = fd.as_fd().as_raw_fd();
+        ClockId((!fd << 3) | CLOCKFD)
+    }
+
     feature! {
     #![feature = "process"]
     /// Returns `ClockId` of a `pid` CPU-time clock
@@ -56,12 +78,120 @@ impl ClockId {
         self.0
     }
 
-    #[cfg(any(linux_android, target_os = "emscripten", target_os = "fuchsia"))]
+    /// Returns all the clocks known to nix on this platform.
+    ///
+    /// Some of them may still be unavailable at runtime, depending on the
+    /// kernel and hardware: on Linux, the `*_ALARM` clocks need a real-time
+    /// clock device.  `CLOCK_SGI_CYCLE` is left out, as Linux never supported
+    /// it.  Constants that are aliases for the same clock, like
+    /// `CLOCK_BOOTTIME` and `CLOCK_UPTIME` on FreeBSD, are listed once.
+    pub fn all() -> &'static [ClockId] {
+        &[
+            // On FreeBSD, this is the same clock as CLOCK_UPTIME
+            #[cfg(any(
+                linux_android,
+                target_os = "emscripten",
+                target_os = "fuchsia",
+                target_os = "openbsd",
+            ))]
+            ClockId::CLOCK_BOOTTIME,
+            #[cfg(any(
+                linux_android,
+                target_os = "emscripten",
+                target_os = "fuchsia"
+            ))]
+            ClockId::CLOCK_BOOTTIME_ALARM,
+            ClockId::CLOCK_MONOTONIC,
+            #[cfg(any(
+                linux_android,
+                target_os = "emscripten",
+                target_os = "fuchsia"
+            ))]
+            ClockId::CLOCK_MONOTONIC_COARSE,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_MONOTONIC_FAST,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_MONOTONIC_PRECISE,
+            #[cfg(any(
+                linux_android,
+                apple_targets,
+                target_os = "emscripten",
+                target_os = "fuchsia"
+            ))]
+            ClockId::CLOCK_MONOTONIC_RAW,
+            #[cfg(any(
+                linux_android,
+                apple_targets,
+                freebsdlike,
+                target_os = "emscripten",
+                target_os = "fuchsia",
+                target_os = "redox",
+            ))]
+            ClockId::CLOCK_PROCESS_CPUTIME_ID,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_PROF,
+            ClockId::CLOCK_REALTIME,
+            #[cfg(any(
+                linux_android,
+                target_os = "emscripten",
+                target_os = "fuchsia"
+            ))]
+            ClockId::CLOCK_REALTIME_ALARM,
+            #[cfg(any(
+                linux_android,
+                target_os = "emscripten",
+                target_os = "fuchsia"
+            ))]
+            ClockId::CLOCK_REALTIME_COARSE,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_REALTIME_FAST,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_REALTIME_PRECISE,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_SECOND,
+            #[cfg(any(
+                linux_android,
+                target_os = "emscripten",
+                target_os = "fuchsia"
+            ))]
+            ClockId::CLOCK_TAI,
+            #[cfg(any(
+                linux_android,
+                apple_targets,
+                freebsdlike,
+                target_os = "emscripten",
+                target_os = "fuchsia",
+            ))]
+            ClockId::CLOCK_THREAD_CPUTIME_ID,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_UPTIME,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_UPTIME_FAST,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_UPTIME_PRECISE,
+            #[cfg(freebsdlike)]
+            ClockId::CLOCK_VIRTUAL,
+        ]
+    }
+
+    #[cfg(any(
+        linux_android,
+        target_os = "emscripten",
+        target_os = "fuchsia",
+        target_os = "freebsd",
+        target_os = "openbsd",
+    ))]
     /// Starts at zero when the kernel boots and increments monotonically in SI seconds while the
     /// machine is running.
+    ///
+    /// Except on FreeBSD, and unlike [`CLOCK_MONOTONIC`](ClockId::CLOCK_MONOTONIC), it also
+    /// counts the time the system spent suspended, so reading it gives the system uptime.  On
+    /// FreeBSD it is an alias for `CLOCK_UPTIME`, which stops while the system is suspended.
+    /// Process start times that the kernel reports relative to boot can be turned into a
+    /// process age by subtracting them from the current value of this clock.
     pub const CLOCK_BOOTTIME: ClockId = ClockId(libc::CLOCK_BOOTTIME);
-    /// Like [`CLOCK_BOOTTIME`](ClockId::CLOCK_BOOTTIME), but will wake the system if it is
-    /// suspended..
+    /// Like [`CLOCK_BOOTTIME`](ClockId::CLOCK_BOOTTIME), but timers based on it will wake the
+    /// system if it is suspended.
     #[cfg(any(linux_android, target_os = "emscripten", target_os = "fuchsia"))]
     pub const CLOCK_BOOTTIME_ALARM: ClockId =
         ClockId(libc::CLOCK_BOOTTIME_ALARM);
@@ -81,7 +211,12 @@ impl ClockId {
         ClockId(libc::CLOCK_MONOTONIC_PRECISE);
     /// Similar to [`CLOCK_MONOTONIC`](ClockId::CLOCK_MONOTONIC), but provides access to a raw
     /// hardware-based time that is not subject to NTP adjustments.
-    #[cfg(any(linux_android, target_os = "emscripten", target_os = "fuchsia"))]
+    #[cfg(any(
+        linux_android,
+        apple_targets,
+        target_os = "emscripten",
+        target_os = "fuchsia"
+    ))]
     pub const CLOCK_MONOTONIC_RAW: ClockId = ClockId(libc::CLOCK_MONOTONIC_RAW);
     #[cfg(any(
         linux_android,
@@ -99,7 +234,8 @@ impl ClockId {
     pub const CLOCK_PROF: ClockId = ClockId(libc::CLOCK_PROF);
     /// Increments as a wall clock should.
     pub const CLOCK_REALTIME: ClockId = ClockId(libc::CLOCK_REALTIME);
-    /// Like [`CLOCK_REALTIME`](ClockId::CLOCK_REALTIME), but not settable.
+    /// Like [`CLOCK_REALTIME`](ClockId::CLOCK_REALTIME), but timers based on it will wake the
+    /// system if it is suspended.
     #[cfg(any(linux_android, target_os = "emscripten", target_os = "fuchsia"))]
     pub const CLOCK_REALTIME_ALARM: ClockId =
         ClockId(libc::CLOCK_REALTIME_ALARM);
@@ -199,6 +335,10 @@ pub fn clock_gettime(clock_id: ClockId) -> Result<TimeSpec> {
 
 /// Set the time of the specified clock, (see
 /// [clock_settime(2)](https://pubs.opengroup.org/onlinepubs/7908799/xsh/clock_settime.html)).
+///
+/// Setting `CLOCK_REALTIME` requires privileges, and fails with `EPERM`
+/// otherwise.  Clocks that can't be set, like `CLOCK_MONOTONIC`, make it fail
+/// with `EINVAL`.
 #[cfg(not(any(
     target_os = "ios",
     target_os = "tvos",
@@ -212,6 +352,26 @@ pub fn clock_settime(clock_id: ClockId, timespec: TimeSpec) -> Result<()> {
     Errno::result(ret).map(drop)
 }
 
+/// Gradually adjust the system clock by `delta`, (see
+/// [adjtime(3)](https://man7.org/linux/man-pages/man3/adjtime.3.html)).
+///
+/// The clock is sped up or slowed down until it has been adjusted by
+/// `delta`, so that it never jumps, unlike with [`clock_settime`].  With
+/// `None`, the clock is left alone.  Either way, the amount of adjustment
+/// still outstanding from a previous call is returned.
+///
+/// Making an adjustment requires privileges, and fails with `EPERM`
+/// otherwise.  On Apple targets and NetBSD, so does a query with `None`.
+#[cfg(any(target_os = "linux", bsd))]
+pub fn adjtime(delta: Option<TimeVal>) -> Result<TimeVal> {
+    let delta = delta.as_ref().map_or(std::ptr::null(), |d| d.as_ref());
+    let mut olddelta: MaybeUninit<libc::timeval> = MaybeUninit::uninit();
+    let ret = unsafe { libc::adjtime(delta, olddelta.as_mut_ptr()) };
+    Errno::result(ret)?;
+    let res = unsafe { olddelta.assume_init() };
+    Ok(TimeVal::from(res))
+}
+
 /// Get the clock id of the specified process id, (see
 /// [clock_getcpuclockid(3)](https://pubs.opengroup.org/onlinepubs/009695399/functions/clock_getcpuclockid.html)).
 #[cfg(any(freebsdlike, linux_android, target_os = "emscripten"))]
@@ -229,6 +389,25 @@ pub fn clock_getcpuclockid(pid: Pid) -> Result<ClockId> {
     }
 }
 
+/// Get the clock id of the calling thread's CPU-time clock, (see
+/// [pthread_getcpuclockid(3)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/pthread_getcpuclockid.html)).
+///
+/// Unlike `CLOCK_THREAD_CPUTIME_ID`, the returned clock keeps referring to
+/// this thread when read from other threads.
+#[cfg(any(freebsdlike, linux_android))]
+pub fn thread_cpu_clock_id() -> Result<ClockId> {
+    let mut clk_id: MaybeUninit<libc::clockid_t> = MaybeUninit::uninit();
+    let ret = unsafe {
+        libc::pthread_getcpuclockid(libc::pthread_self(), clk_id.as_mut_ptr())
+    };
+    if ret == 0 {
+        let res = unsafe { clk_id.assume_init() };
+        Ok(ClockId::from(res))
+    } else {
+        Err(Errno::from_raw(ret))
+    }
+}
+
 #[cfg(any(
     linux_android,
     solarish,
@@ -254,6 +433,40 @@ libc_bitflags! {
 /// a signal is caught by a signal-catching function, or a signal causes the process to terminate,
 /// this sleep is interrrupted.
 ///
+/// An interrupted sleep fails with `EINTR`, so that the caller can decide
+/// whether to resume it.  The time that remained is not reported, so a sleep
+/// that must be resumed is best expressed with an absolute deadline, which
+/// can simply be restarted with the same `request`.
+///
+/// # Examples
+///
+/// Absolute deadlines make periodic loops free of drift:
+///
+/// ```
+/// # use nix::errno::Errno;
+/// # use nix::sys::time::{TimeSpec, TimeValLike};
+/// # use nix::time::{clock_nanosleep, ClockId, ClockNanosleepFlags};
+/// let period = TimeSpec::milliseconds(1);
+/// let mut deadline = ClockId::CLOCK_MONOTONIC.now().unwrap();
+/// for _ in 0..3 {
+///     deadline = deadline + period;
+///     loop {
+///         match clock_nanosleep(
+///             ClockId::CLOCK_MONOTONIC,
+///             ClockNanosleepFlags::TIMER_ABSTIME,
+///             &deadline,
+///         ) {
+///             Err(Errno::EINTR) => continue,
+///             r => {
+///                 r.unwrap();
+///                 break;
+///             }
+///         }
+///     }
+///     // Do periodic work here
+/// }
+/// ```
+///
 /// see also [man 3 clock_nanosleep](https://pubs.opengroup.org/onlinepubs/009695399/functions/clock_nanosleep.html)
 #[cfg(any(
     linux_android,
@@ -267,19 +480,248 @@ pub fn clock_nanosleep(
     clock_id: ClockId,
     flags: ClockNanosleepFlags,
     request: &TimeSpec,
-) -> Result<TimeSpec> {
-    let mut remain = TimeSpec::new(0, 0);
+) -> Result<()> {
     let ret = unsafe {
         libc::clock_nanosleep(
             clock_id.as_raw(),
             flags.bits(),
             request.as_ref() as *const _,
-            remain.as_mut() as *mut _,
+            std::ptr::null_mut(),
         )
     };
     if ret == 0 {
-        Ok(remain)
+        Ok(())
     } else {
         Err(Errno::from_raw(ret))
     }
 }
+
+#[cfg(target_os = "linux")]
+libc_bitflags! {
+    /// Selects which fields of a [`TimeX`] are written by [`clock_adjtime`].
+    ///
+    /// With no flag set, the clock is only read.
+    pub struct AdjTimexModes: libc::c_uint {
+        /// Set the time offset from [`TimeX::offset`].
+        ADJ_OFFSET;
+        /// Set the frequency offset from [`TimeX::freq`].
+        ADJ_FREQUENCY;
+        /// Set the maximum time error from [`TimeX::maxerror`].
+        ADJ_MAXERROR;
+        /// Set the estimated time error.
+        ADJ_ESTERROR;
+        /// Set the clock status bits from [`TimeX::status`].
+        ADJ_STATUS;
+        /// Set the PLL time constant.
+        ADJ_TIMECONST;
+        /// Set the TAI offset.
+        ADJ_TAI;
+        /// Add the offset in `time` to the current time.
+        ADJ_SETOFFSET;
+        /// Use microsecond resolution for the offset.
+        ADJ_MICRO;
+        /// Use nanosecond resolution for the offset.
+        ADJ_NANO;
+        /// Set the tick value.
+        ADJ_TICK;
+    }
+}
+
+#[cfg(target_os = "linux")]
+libc_bitflags! {
+    /// Clock status bits, as found in [`TimeX::status`].
+    pub struct TimeXStatus: libc::c_int {
+        /// Enable phase-locked loop updates.
+        STA_PLL;
+        /// Enable PPS frequency discipline.
+        STA_PPSFREQ;
+        /// Enable PPS time discipline.
+        STA_PPSTIME;
+        /// Select frequency-locked loop mode.
+        STA_FLL;
+        /// Insert a leap second at the end of the current UTC day.
+        STA_INS;
+        /// Delete a leap second at the end of the current UTC day.
+        STA_DEL;
+        /// The clock is not synchronized.
+        STA_UNSYNC;
+        /// Hold the frequency.
+        STA_FREQHOLD;
+        /// A valid PPS signal is present (read-only).
+        STA_PPSSIGNAL;
+        /// PPS signal jitter exceeded (read-only).
+        STA_PPSJITTER;
+        /// PPS signal wander exceeded (read-only).
+        STA_PPSWANDER;
+        /// PPS signal calibration error (read-only).
+        STA_PPSERROR;
+        /// Clock hardware fault (read-only).
+        STA_CLOCKERR;
+        /// Resolution is nanoseconds instead of microseconds (read-only).
+        STA_NANO;
+        /// FLL mode instead of PLL mode (read-only).
+        STA_MODE;
+        /// Clock source is B instead of A (read-only).
+        STA_CLK;
+    }
+}
+
+/// The clock state returned by [`clock_adjtime`] and [`adjtimex`].
+#[cfg(target_os = "linux")]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum AdjTimexStatus {
+    /// The clock is synchronized, and no leap second is pending.
+    TIME_OK,
+    /// A leap second will be added at the end of the UTC day.
+    TIME_INS,
+    /// A leap second will be deleted at the end of the UTC day.
+    TIME_DEL,
+    /// A leap second insertion is in progress.
+    TIME_OOP,
+    /// A leap second has just occurred.
+    TIME_WAIT,
+    /// The clock is not synchronized.
+    TIME_ERROR,
+    /// A state not known to nix.
+    Unknown(libc::c_int),
+}
+
+#[cfg(target_os = "linux")]
+impl From<libc::c_int> for AdjTimexStatus {
+    fn from(state: libc::c_int) -> Self {
+        match state {
+            libc::TIME_OK => AdjTimexStatus::TIME_OK,
+            libc::TIME_INS => AdjTimexStatus::TIME_INS,
+            libc::TIME_DEL => AdjTimexStatus::TIME_DEL,
+            libc::TIME_OOP => AdjTimexStatus::TIME_OOP,
+            libc::TIME_WAIT => AdjTimexStatus::TIME_WAIT,
+            libc::TIME_ERROR => AdjTimexStatus::TIME_ERROR,
+            state => AdjTimexStatus::Unknown(state),
+        }
+    }
+}
+
+/// Parameters of the kernel clock discipline, as used by [`clock_adjtime`].
+///
+/// Newtype pattern around [`libc::timex`].  A default `TimeX` has all fields,
+/// including [`modes`](TimeX::modes), set to zero, which only reads the clock.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(transparent)]
+pub struct TimeX(libc::timex);
+
+#[cfg(target_os = "linux")]
+impl Default for TimeX {
+    fn default() -> Self {
+        // SAFETY: all-zero is a valid `timex`
+        TimeX(unsafe { std::mem::zeroed() })
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl TimeX {
+    /// Which fields [`clock_adjtime`] will write.
+    pub fn modes(&self) -> AdjTimexModes {
+        AdjTimexModes::from_bits_retain(self.0.modes)
+    }
+
+    /// Set which fields [`clock_adjtime`] will write.
+    pub fn set_modes(&mut self, modes: AdjTimexModes) {
+        self.0.modes = modes.bits();
+    }
+
+    /// Time offset, in microseconds, or nanoseconds if
+    /// [`STA_NANO`](TimeXStatus::STA_NANO) is set.
+    #[allow(clippy::unnecessary_cast)] // Not unnecessary on x32
+    pub fn offset(&self) -> i64 {
+        self.0.offset as i64
+    }
+
+    /// Set the time offset.  Requires [`AdjTimexModes::ADJ_OFFSET`].
+    pub fn set_offset(&mut self, offset: i64) {
+        self.0.offset = offset as _;
+    }
+
+    /// Frequency offset, in parts per million with a 16-bit fractional part.
+    #[allow(clippy::unnecessary_cast)] // Not unnecessary on x32
+    pub fn freq(&self) -> i64 {
+        self.0.freq as i64
+    }
+
+    /// Set the frequency offset.  Requires
+    /// [`AdjTimexModes::ADJ_FREQUENCY`].
+    pub fn set_freq(&mut self, freq: i64) {
+        self.0.freq = freq as _;
+    }
+
+    /// Maximum error, in microseconds.
+    #[allow(clippy::unnecessary_cast)] // Not unnecessary on x32
+    pub fn maxerror(&self) -> i64 {
+        self.0.maxerror as i64
+    }
+
+    /// Set the maximum error.  Requires [`AdjTimexModes::ADJ_MAXERROR`].
+    pub fn set_maxerror(&mut self, maxerror: i64) {
+        self.0.maxerror = maxerror as _;
+    }
+
+    /// Clock status bits.
+    pub fn status(&self) -> TimeXStatus {
+        TimeXStatus::from_bits_retain(self.0.status)
+    }
+
+    /// Set the clock status bits.  Requires [`AdjTimexModes::ADJ_STATUS`].
+    pub fn set_status(&mut self, status: TimeXStatus) {
+        self.0.status = status.bits();
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl AsRef<libc::timex> for TimeX {
+    fn as_ref(&self) -> &libc::timex {
+        &self.0
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl AsMut<libc::timex> for TimeX {
+    fn as_mut(&mut self) -> &mut libc::timex {
+        &mut self.0
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<libc::timex> for TimeX {
+    fn from(tx: libc::timex) -> Self {
+        TimeX(tx)
+    }
+}
+
+/// Read or tune the kernel discipline of the specified clock, (see
+/// [clock_adjtime(2)](https://man7.org/linux/man-pages/man2/clock_adjtime.2.html)).
+///
+/// The fields selected by `tx`'s [`modes`](TimeX::modes) are written first,
+/// which requires `CAP_SYS_TIME`; then `tx` is filled in with the current
+/// parameters.  Returns the clock state, e.g. whether a leap second is
+/// pending.
+#[cfg(target_os = "linux")]
+pub fn clock_adjtime(
+    clock_id: ClockId,
+    tx: &mut TimeX,
+) -> Result<AdjTimexStatus> {
+    let ret = unsafe { libc::clock_adjtime(clock_id.as_raw(), &mut tx.0) };
+    Errno::result(ret).map(AdjTimexStatus::from)
+}
+
+/// Read or tune the kernel discipline of the system clock, (see
+/// [adjtimex(2)](https://man7.org/linux/man-pages/man2/adjtimex.2.html)).
+///
+/// Equivalent to [`clock_adjtime`] on
+/// [`CLOCK_REALTIME`](ClockId::CLOCK_REALTIME).
+#[cfg(target_os = "linux")]
+pub fn adjtimex(tx: &mut TimeX) -> Result<AdjTimexStatus> {
+    let ret = unsafe { libc::adjtimex(&mut tx.0) };
+    Errno::result(ret).map(AdjTimexStatus::from)
+}
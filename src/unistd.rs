@@ -28,6 +28,8 @@ use std::convert::Infallible;
 #[cfg(not(target_os = "redox"))]
 use std::ffi::CString;
 use std::ffi::{CStr, OsStr, OsString};
+#[cfg(all(feature = "process", target_os = "linux"))]
+use std::os::fd::OwnedFd;
 use std::os::unix::ffi::{OsStrExt, OsStringExt};
 use std::path::PathBuf;
 use std::{fmt, mem, ptr};
@@ -185,6 +187,29 @@ impl Pid {
     pub const fn as_raw(self) -> pid_t {
         self.0
     }
+
+    /// Check whether a process with this PID exists.
+    ///
+    /// This sends the null signal with `kill(pid, 0)`, which performs error
+    /// checking without delivering anything.  A process that exists but that
+    /// the caller lacks permission to signal (`EPERM`) is still reported as
+    /// existing.  A zombie that has not yet been reaped also counts as existing.
+    ///
+    /// PIDs are recycled, so a `true` result does not guarantee that this is
+    /// still the process the caller had in mind.
+    ///
+    /// Returns `EINVAL` if `self` is not positive, because `kill` would then
+    /// refer to a process group rather than a single process.
+    pub fn exists(&self) -> Result<bool> {
+        if self.0 <= 0 {
+            return Err(Errno::EINVAL);
+        }
+        match Errno::result(unsafe { libc::kill(self.0, 0) }) {
+            Ok(_) | Err(Errno::EPERM) => Ok(true),
+            Err(Errno::ESRCH) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 impl From<Pid> for pid_t {
@@ -318,6 +343,21 @@ pub fn setpgid(pid: Pid, pgid: Pid) -> Result<()> {
     let res = unsafe { libc::setpgid(pid.into(), pgid.into()) };
     Errno::result(res).map(drop)
 }
+
+/// Make the calling process the leader of a new process group.
+///
+/// This is `setpgid(0, 0)`: the zero pid selects the calling process and the
+/// zero pgid means "use the pid as the group id".  Job-control shells call it
+/// in each child they spawn before handing it the terminal.  Fails with
+/// `EPERM` if the caller is already a session leader.
+///
+/// # See Also
+/// [`setpgid`](fn.setpgid.html), [`getpgrp`](fn.getpgrp.html)
+#[inline]
+pub fn setpgid_self() -> Result<()> {
+    setpgid(Pid(0), Pid(0))
+}
+
 /// Get process group
 ///
 /// See Also [`getpgid`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/getpgid.html)
@@ -402,6 +442,70 @@ pub fn getpgrp() -> Pid {
 pub fn gettid() -> Pid {
     Pid(unsafe { libc::syscall(libc::SYS_gettid) as pid_t })
 }
+
+#[cfg(target_os = "linux")]
+libc_bitflags! {
+    /// Flags for [`pidfd_open`].
+    pub struct PidfdFlags: c_uint {
+        /// Make [`waitid`](crate::sys::wait::waitid) on the file descriptor
+        /// fail with `EAGAIN` rather than block, if the process is still
+        /// running.  Since Linux 5.10.
+        PIDFD_NONBLOCK;
+        /// Refer to the thread `pid` rather than to the process whose thread
+        /// group leader it is.  Since Linux 6.9.
+        PIDFD_THREAD;
+    }
+}
+
+/// Obtain a file descriptor that refers to the process `pid` (see
+/// [pidfd_open(2)](https://man7.org/linux/man-pages/man2/pidfd_open.2.html)).
+///
+/// Available since Linux 5.3.
+#[cfg(target_os = "linux")]
+pub fn pidfd_open(pid: Pid, flags: PidfdFlags) -> Result<OwnedFd> {
+    use std::os::fd::FromRawFd;
+
+    let res =
+        unsafe { libc::syscall(libc::SYS_pidfd_open, pid.0, flags.bits()) };
+    Errno::result(res)?;
+    // SAFETY:
+    //
+    // `pidfd_open(2)` returns a new file descriptor on success
+    Ok(unsafe { OwnedFd::from_raw_fd(res as c_int) })
+}
+
+/// Duplicate the file descriptor `targetfd` of the process referred to by
+/// `pidfd` into the calling process (see
+/// [pidfd_getfd(2)](https://man7.org/linux/man-pages/man2/pidfd_getfd.2.html)).
+///
+/// The new file descriptor has the close-on-exec flag set.  `flags` is
+/// reserved and must be `0`.
+///
+/// The caller needs `PTRACE_MODE_ATTACH_REALCREDS` permission over the target
+/// process, otherwise `EPERM` is returned.  Available since Linux 5.6.
+#[cfg(target_os = "linux")]
+pub fn pidfd_getfd<Fd: std::os::fd::AsFd>(
+    pidfd: Fd,
+    targetfd: std::os::fd::RawFd,
+    flags: u32,
+) -> Result<OwnedFd> {
+    use std::os::fd::AsRawFd;
+    use std::os::fd::FromRawFd;
+
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_pidfd_getfd,
+            pidfd.as_fd().as_raw_fd(),
+            targetfd,
+            flags,
+        )
+    };
+    Errno::result(res)?;
+    // SAFETY:
+    //
+    // `pidfd_getfd(2)` returns a new file descriptor on success
+    Ok(unsafe { OwnedFd::from_raw_fd(res as c_int) })
+}
 }
 
 feature! {
@@ -510,6 +614,43 @@ pub fn dup2_stderr<Fd: std::os::fd::AsFd>(fd: Fd) -> Result<()> {
     Errno::result(res).map(drop)
 }
 
+/// Redirect the standard streams to the given file descriptors, typically in
+/// a child process before `exec`.
+///
+/// Each given descriptor is duplicated onto stdin, stdout or stderr as with
+/// [`dup2_stdin()`], [`dup2_stdout()`] and [`dup2_stderr()`]; streams given
+/// as `None` are left alone.  The standard descriptors are left without
+/// `FD_CLOEXEC`, so that they are inherited across `exec`, even if a given
+/// descriptor already was the standard one.
+///
+/// For `posix_spawn`, use the `add_dup2` file action of
+/// `spawn::PosixSpawnFileActions` instead.
+pub fn redirect_stdio(
+    stdin: Option<std::os::fd::BorrowedFd<'_>>,
+    stdout: Option<std::os::fd::BorrowedFd<'_>>,
+    stderr: Option<std::os::fd::BorrowedFd<'_>>,
+) -> Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let streams = [
+        (stdin, libc::STDIN_FILENO),
+        (stdout, libc::STDOUT_FILENO),
+        (stderr, libc::STDERR_FILENO),
+    ];
+    for (fd, stdfd) in streams {
+        let Some(fd) = fd else { continue };
+        if fd.as_raw_fd() == stdfd {
+            // dup2 does nothing in this case, so clear the flag by hand
+            let flags = unsafe { libc::fcntl(stdfd, libc::F_GETFD) };
+            let flags = Errno::result(flags)? & !libc::FD_CLOEXEC;
+            Errno::result(unsafe { libc::fcntl(stdfd, libc::F_SETFD, flags) })?;
+        } else {
+            Errno::result(unsafe { libc::dup2(fd.as_raw_fd(), stdfd) })?;
+        }
+    }
+    Ok(())
+}
+
 /// Create a copy of `oldfd` using `newfd`.
 ///
 /// This function behaves similar to `dup()` except that it will try to use the
@@ -738,6 +879,54 @@ pub fn fchdir<Fd: std::os::fd::AsFd>(dirfd: Fd) -> Result<()> {
     Errno::result(res).map(drop)
 }
 
+/// Changes the current working directory for as long as it lives, and
+/// changes it back when dropped.
+///
+/// The original directory is kept open, so it is restored with [`fchdir`]
+/// even if it was renamed in the meantime, and even if the scope is left early
+/// through `?` or a panic.  Errors when restoring it are ignored.
+///
+/// The working directory is shared by all threads of the process, so
+/// concurrent users must synchronize with each other.
+///
+/// # Examples
+///
+/// ```no_run
+/// use nix::unistd::ChdirGuard;
+///
+/// {
+///     let _guard = ChdirGuard::new("/tmp").unwrap();
+///     // The working directory is /tmp here.
+/// }
+/// // It is back to the original one here.
+/// ```
+#[cfg(not(target_os = "fuchsia"))]
+#[derive(Debug)]
+pub struct ChdirGuard {
+    saved: std::os::fd::OwnedFd,
+}
+
+#[cfg(not(target_os = "fuchsia"))]
+impl ChdirGuard {
+    /// Open the current working directory, then change it to `path`.
+    pub fn new<P: ?Sized + NixPath>(path: &P) -> Result<Self> {
+        let saved = crate::fcntl::open(
+            ".",
+            OFlag::O_RDONLY | OFlag::O_CLOEXEC,
+            crate::sys::stat::Mode::empty(),
+        )?;
+        chdir(path)?;
+        Ok(ChdirGuard { saved })
+    }
+}
+
+#[cfg(not(target_os = "fuchsia"))]
+impl Drop for ChdirGuard {
+    fn drop(&mut self) {
+        let _ = fchdir(&self.saved);
+    }
+}
+
 /// Creates new directory `path` with access rights `mode`.  (see [mkdir(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/mkdir.html))
 ///
 /// # Errors
@@ -1308,6 +1497,8 @@ pub fn sethostname<S: AsRef<OsStr>>(name: S) -> Result<()> {
 ///
 /// This function call attempts to get the host name for the running system and
 /// store it in an internal buffer, returning it as an `OsString` if successful.
+/// If the host name doesn't fit in the buffer, the buffer is grown and the call
+/// retried, so that the result is never truncated.
 ///
 /// # Examples
 ///
@@ -1321,20 +1512,58 @@ pub fn sethostname<S: AsRef<OsStr>>(name: S) -> Result<()> {
 ///
 /// See also [gethostname(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/gethostname.html).
 pub fn gethostname() -> Result<OsString> {
-    // The capacity is the max length of a hostname plus the NUL terminator.
-    let mut buffer: Vec<u8> = Vec::with_capacity(256);
-    let ptr = buffer.as_mut_ptr().cast();
-    let len = buffer.capacity() as size_t;
+    // The initial capacity is the max length of a hostname on most systems,
+    // plus the NUL terminator.
+    let mut len = 256;
+    // No system allows host names anywhere near this long
+    const MAX_LEN: usize = 64 * 1024;
 
-    let res = unsafe { libc::gethostname(ptr, len) };
-    Errno::result(res).map(|_| {
-        unsafe {
-            buffer.as_mut_ptr().wrapping_add(len - 1).write(0); // ensure always null-terminated
-            let len = CStr::from_ptr(buffer.as_ptr().cast()).len();
-            buffer.set_len(len);
+    loop {
+        let mut buffer = vec![0u8; len];
+        let res = unsafe {
+            libc::gethostname(buffer.as_mut_ptr().cast(), len as size_t)
+        };
+        match Errno::result(res) {
+            Ok(_) => {
+                // On truncation, some systems succeed without writing a NUL
+                // terminator.
+                if let Some(end) = buffer.iter().position(|&b| b == 0) {
+                    buffer.truncate(end);
+                    return Ok(OsString::from_vec(buffer));
+                }
+            }
+            // Others report the truncation as an error.
+            Err(Errno::ENAMETOOLONG | Errno::EINVAL) if len < MAX_LEN => (),
+            Err(e) => return Err(e),
         }
-        OsString::from_vec(buffer)
-    })
+        if len >= MAX_LEN {
+            return Err(Errno::ENAMETOOLONG);
+        }
+        len *= 2;
+    }
+}
+}
+
+#[cfg(not(any(
+    target_os = "redox",
+    target_os = "fuchsia",
+    solarish,
+    target_os = "haiku"
+)))]
+feature! {
+#![feature = "resource"]
+/// Get the maximum number of file descriptors the process may have open,
+/// which is one more than the highest descriptor it may open.
+///
+/// This is the modern equivalent of the legacy `getdtablesize()`: the current
+/// (soft) `RLIMIT_NOFILE` limit, as returned by
+/// [`getrlimit`](crate::sys::resource::getrlimit).  An unlimited limit is
+/// returned as `usize::MAX`.
+pub fn getdtablesize() -> Result<usize> {
+    use crate::sys::resource::{getrlimit, Resource};
+
+    let (soft, _) = getrlimit(Resource::RLIMIT_NOFILE)?;
+    Ok(usize::try_from(soft).unwrap_or(usize::MAX))
 }
 }
 
@@ -1347,6 +1576,170 @@ pub fn close<Fd: std::os::fd::IntoRawFd>(fd: Fd) -> Result<()> {
     Errno::result(res).map(drop)
 }
 
+#[cfg(any(target_os = "freebsd", target_os = "linux"))]
+libc_bitflags! {
+    /// Options for [`close_range`].
+    pub struct CloseRangeFlags: c_uint {
+        /// Set the close-on-exec flag on the file descriptors instead of
+        /// closing them.
+        CLOSE_RANGE_CLOEXEC;
+        /// Unshare the file descriptor table from other processes before
+        /// closing the file descriptors, as with `unshare(CLONE_FILES)`.
+        #[cfg(target_os = "linux")]
+        CLOSE_RANGE_UNSHARE;
+    }
+}
+
+/// Close all the file descriptors from `first` to `last`, inclusive.
+///
+/// Closing every inherited file descriptor above stderr before `exec` is a
+/// common hardening step: `close_range(3, RawFd::MAX, CloseRangeFlags::empty())`.
+/// File descriptors in the range that aren't open are skipped.
+///
+/// On Linux kernels older than 5.9, which lack the system call, this falls
+/// back to walking `/proc/self/fd`, except when
+/// [`CLOSE_RANGE_UNSHARE`](CloseRangeFlags::CLOSE_RANGE_UNSHARE) is given.
+/// The fallback doesn't allocate either, so both are safe to use between
+/// `fork` and `exec`.  It fails with the error from opening `/proc/self/fd`,
+/// e.g. `ENOENT` if `/proc` isn't mounted.
+///
+/// # Safety
+///
+/// Any `OwnedFd` or `BorrowedFd` in the range becomes dangling once its file
+/// descriptor is closed.  The caller must make sure that nothing in the
+/// process, including other threads, still uses them.  This is trivially the
+/// case right before an `exec`.
+///
+/// # See Also
+/// * [close_range(2)](https://man7.org/linux/man-pages/man2/close_range.2.html)
+/// * [close_range(2) on FreeBSD](https://man.freebsd.org/cgi/man.cgi?query=close_range)
+#[cfg(any(target_os = "freebsd", target_os = "linux"))]
+pub unsafe fn close_range(
+    first: std::os::fd::RawFd,
+    last: std::os::fd::RawFd,
+    flags: CloseRangeFlags,
+) -> Result<()> {
+    if first < 0 || last < first {
+        return Err(Errno::EINVAL);
+    }
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            let res = unsafe {
+                libc::syscall(
+                    libc::SYS_close_range,
+                    first as c_uint,
+                    last as c_uint,
+                    flags.bits(),
+                )
+            };
+            match Errno::result(res) {
+                Err(Errno::ENOSYS)
+                    if !flags.contains(CloseRangeFlags::CLOSE_RANGE_UNSHARE) =>
+                {
+                    close_range_fallback(first, last, flags)
+                }
+                r => r.map(drop),
+            }
+        } else {
+            let res = unsafe {
+                libc::close_range(
+                    first as c_uint,
+                    last as c_uint,
+                    flags.bits() as c_int,
+                )
+            };
+            Errno::result(res).map(drop)
+        }
+    }
+}
+
+/// Emulates `close_range` by walking `/proc/self/fd`.
+///
+/// Like `close_range` itself, this doesn't allocate, so that it can be used
+/// between `fork` and `exec`: the directory is read with `getdents64` into a
+/// buffer on the stack.
+#[cfg(target_os = "linux")]
+fn close_range_fallback(
+    first: std::os::fd::RawFd,
+    last: std::os::fd::RawFd,
+    flags: CloseRangeFlags,
+) -> Result<()> {
+    let path = b"/proc/self/fd\0";
+    let dirfd = Errno::result(unsafe {
+        libc::open(
+            path.as_ptr().cast(),
+            libc::O_RDONLY | libc::O_DIRECTORY | libc::O_CLOEXEC,
+        )
+    })?;
+
+    // Closing entries while reading the directory is fine: its offsets are
+    // file descriptor numbers.
+    let mut buf = [0u64; 512];
+    let res = loop {
+        let n = unsafe {
+            libc::syscall(
+                libc::SYS_getdents64,
+                dirfd,
+                buf.as_mut_ptr(),
+                mem::size_of_val(&buf),
+            )
+        };
+        let n = match Errno::result(n) {
+            Ok(0) => break Ok(()),
+            Ok(n) => n as usize,
+            Err(e) => break Err(e),
+        };
+        let mut offset = 0;
+        while offset < n {
+            // The kernel filled the first `n` bytes of `buf` with 8-byte
+            // aligned records.  The last one may be shorter than a whole
+            // `dirent64`, so only its fields are accessed, never the struct.
+            #[allow(clippy::cast_ptr_alignment)]
+            let entry = unsafe {
+                buf.as_ptr()
+                    .cast::<u8>()
+                    .add(offset)
+                    .cast::<libc::dirent64>()
+            };
+            let reclen = unsafe { ptr::addr_of!((*entry).d_reclen).read() };
+            offset += reclen as usize;
+
+            let name = unsafe {
+                CStr::from_ptr(ptr::addr_of!((*entry).d_name).cast())
+            };
+            // Skips "." and ".."
+            let Some(fd) = name
+                .to_str()
+                .ok()
+                .and_then(|name| name.parse::<std::os::fd::RawFd>().ok())
+            else {
+                continue;
+            };
+            if fd == dirfd || !(first..=last).contains(&fd) {
+                continue;
+            }
+            // Errors are ignored: the fd can only have been closed since
+            // the directory was read.
+            unsafe {
+                if flags.contains(CloseRangeFlags::CLOSE_RANGE_CLOEXEC) {
+                    let fdflags = libc::fcntl(fd, libc::F_GETFD);
+                    if fdflags >= 0 {
+                        libc::fcntl(
+                            fd,
+                            libc::F_SETFD,
+                            fdflags | libc::FD_CLOEXEC,
+                        );
+                    }
+                } else {
+                    libc::close(fd);
+                }
+            }
+        }
+    };
+    unsafe { libc::close(dirfd) };
+    res
+}
+
 /// Read from a raw file descriptor.
 ///
 /// See also [read(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/read.html)
@@ -1364,6 +1757,63 @@ pub fn read<Fd: std::os::fd::AsFd>(fd: Fd, buf: &mut [u8]) -> Result<usize> {
     Errno::result(res).map(|r| r as usize)
 }
 
+/// Read exactly enough bytes from a raw file descriptor to fill `buf`.
+///
+/// Unlike [`read`], this retries short reads and reads interrupted by a signal
+/// (`EINTR`).  Fails with `EIO` if end-of-file is reached before `buf` is full,
+/// in which case the contents of `buf` are unspecified.
+///
+/// See also [read(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/read.html)
+pub fn read_exact<Fd: std::os::fd::AsFd>(
+    fd: Fd,
+    mut buf: &mut [u8],
+) -> Result<()> {
+    let fd = fd.as_fd();
+    while !buf.is_empty() {
+        match read(fd, buf) {
+            Ok(0) => return Err(Errno::EIO),
+            Ok(n) => buf = &mut buf[n..],
+            Err(Errno::EINTR) => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Read all bytes from a raw file descriptor until end-of-file, appending them
+/// to `buf`.  Returns the number of bytes read.
+///
+/// Reads interrupted by a signal (`EINTR`) are retried.  If an error occurs,
+/// the bytes read so far are left in `buf`.
+///
+/// See also [read(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/read.html)
+pub fn read_to_end<Fd: std::os::fd::AsFd>(
+    fd: Fd,
+    buf: &mut Vec<u8>,
+) -> Result<usize> {
+    const CHUNK: usize = 8192;
+
+    let fd = fd.as_fd();
+    let start = buf.len();
+    loop {
+        let len = buf.len();
+        buf.resize(len + CHUNK, 0);
+        match read(fd, &mut buf[len..]) {
+            Ok(n) => {
+                buf.truncate(len + n);
+                if n == 0 {
+                    return Ok(buf.len() - start);
+                }
+            }
+            Err(Errno::EINTR) => buf.truncate(len),
+            Err(e) => {
+                buf.truncate(len);
+                return Err(e);
+            }
+        }
+    }
+}
+
 /// Write to a raw file descriptor.
 ///
 /// See also [write(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/write.html)
@@ -1381,6 +1831,30 @@ pub fn write<Fd: std::os::fd::AsFd>(fd: Fd, buf: &[u8]) -> Result<usize> {
     Errno::result(res).map(|r| r as usize)
 }
 
+/// Write the whole of `buf` to a raw file descriptor.
+///
+/// Unlike [`write`], this retries partial writes and writes interrupted by a
+/// signal (`EINTR`) until all of `buf` is written.  Fails with `EIO` if the
+/// file descriptor accepts no more data without reporting an error.
+///
+/// This only calls `write(2)`, so it is async-signal-safe: it can be used in
+/// signal handlers, and in the child of a multi-threaded process between
+/// `fork` and `exec`.  Note that it may change `errno`.
+///
+/// See also [write(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/write.html)
+pub fn write_all<Fd: std::os::fd::AsFd>(fd: Fd, mut buf: &[u8]) -> Result<()> {
+    let fd = fd.as_fd();
+    while !buf.is_empty() {
+        match write(fd, buf) {
+            Ok(0) => return Err(Errno::EIO),
+            Ok(n) => buf = &buf[n..],
+            Err(Errno::EINTR) => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
 feature! {
 #![feature = "fs"]
 
@@ -1821,6 +2295,19 @@ feature! {
 /// On both success and failure, this call returns the previous filesystem user
 /// ID of the caller.
 ///
+/// Failure is not reported through `errno`.  To tell whether the change took
+/// effect, call this a second time with an invalid ID such as `u32::MAX`,
+/// which changes nothing and returns the current filesystem user ID.
+///
+/// ```no_run
+/// # use nix::unistd::{setfsuid, Uid};
+/// let uid = Uid::from_raw(1000);
+/// setfsuid(uid);
+/// if setfsuid(Uid::from_raw(u32::MAX)) != uid {
+///     // the filesystem user ID was not changed
+/// }
+/// ```
+///
 /// See also [setfsuid(2)](https://man7.org/linux/man-pages/man2/setfsuid.2.html)
 #[cfg(linux_android)]
 pub fn setfsuid(uid: Uid) -> Uid {
@@ -1832,6 +2319,9 @@ pub fn setfsuid(uid: Uid) -> Uid {
 /// On both success and failure, this call returns the previous filesystem group
 /// ID of the caller.
 ///
+/// Like with [`setfsuid`], failure is not reported through `errno`; call this
+/// again with `u32::MAX` to read back the current filesystem group ID.
+///
 /// See also [setfsgid(2)](https://man7.org/linux/man-pages/man2/setfsgid.2.html)
 #[cfg(linux_android)]
 pub fn setfsgid(gid: Gid) -> Gid {
@@ -2095,11 +2585,17 @@ feature! {
 
 /// Suspend the thread until a signal is received.
 ///
+/// `pause` only returns once a signal handler has run, and then always fails
+/// with `EINTR`; it never returns `Ok`.  Signals that are ignored, or whose
+/// default action is to do nothing, do not wake it up.
+///
 /// See also [pause(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/pause.html).
 #[inline]
 #[cfg(not(target_os = "redox"))]
-pub fn pause() {
-    unsafe { libc::pause() };
+pub fn pause() -> Result<()> {
+    let res = unsafe { libc::pause() };
+
+    Errno::result(res).map(drop)
 }
 
 pub mod alarm {
@@ -2162,6 +2658,14 @@ pub mod alarm {
     //! assert!(start.elapsed() + TOLERANCE >= Duration::from_secs(1));
     //! ```
     //!
+    //! # Interactions
+    //!
+    //! A process has a single alarm: setting one replaces any alarm set
+    //! before, including by other threads or libraries.  The alarm may also
+    //! share its timer with `setitimer(ITIMER_REAL)`, and mixing it with
+    //! `sleep(3)` is unspecified by POSIX.  Use `timer_create` when several
+    //! independent timers are needed.
+    //!
     //! # References
     //!
     //! See also [alarm(2)](https://pubs.opengroup.org/onlinepubs/9699919799/functions/alarm.html).
@@ -2177,6 +2681,22 @@ pub mod alarm {
         alarm(secs)
     }
 
+    /// Schedule an alarm signal after a [`Duration`](std::time::Duration).
+    ///
+    /// `alarm` has a resolution of one second, so `timeout` is rounded up to
+    /// the next whole second.  Returns the leftover time of a previously set
+    /// alarm if there was one.
+    pub fn set_duration(
+        timeout: std::time::Duration,
+    ) -> Option<std::time::Duration> {
+        assert!(!timeout.is_zero(), "passing a zero duration to `alarm::set_duration` is not allowed, to cancel an alarm use `alarm::cancel`");
+        let secs = timeout
+            .as_secs()
+            .saturating_add(u64::from(timeout.subsec_nanos() != 0));
+        let secs = libc::c_uint::try_from(secs).unwrap_or(libc::c_uint::MAX);
+        alarm(secs).map(|left| std::time::Duration::from_secs(left.into()))
+    }
+
     /// Cancel an previously set alarm signal.
     ///
     /// Returns the leftover time of a previously set alarm if there was one.
@@ -3328,6 +3848,32 @@ mod getres {
             saved: Gid(sgid),
         })
     }
+
+    /// All the user and group IDs of a process.
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct Credentials {
+        /// Real, effective and saved user IDs
+        pub uid: ResUid,
+        /// Real, effective and saved group IDs
+        pub gid: ResGid,
+        /// Supplementary group IDs
+        pub groups: Vec<Gid>,
+    }
+
+    impl Credentials {
+        /// Get all the user and group IDs of the calling process at once,
+        /// e.g. to check them before and after dropping privileges.
+        ///
+        /// This combines [`getresuid`], [`getresgid`] and
+        /// [`getgroups`](super::getgroups).
+        pub fn current() -> Result<Credentials> {
+            Ok(Credentials {
+                uid: getresuid()?,
+                gid: getresgid()?,
+                groups: super::getgroups()?,
+            })
+        }
+    }
     }
 }
 
@@ -3940,6 +4486,10 @@ feature! {
 
 /// Get the name of the terminal device that is open on file descriptor fd
 /// (see [`ttyname(3)`](https://man7.org/linux/man-pages/man3/ttyname.3.html)).
+///
+/// This uses the thread-safe `ttyname_r`, growing the buffer as needed.  Fails
+/// with `ENOTTY` if `fd` is not a terminal; use [`isatty`] to check that
+/// first.
 #[cfg(not(target_os = "fuchsia"))]
 pub fn ttyname<F: std::os::fd::AsFd>(fd: F) -> Result<PathBuf> {
     use std::os::fd::AsRawFd;
@@ -3949,17 +4499,60 @@ pub fn ttyname<F: std::os::fd::AsFd>(fd: F) -> Result<PathBuf> {
     #[cfg(target_os = "hurd")]
     const PATH_MAX: usize = 1024; // Hurd does not define a hard limit, so try a guess first
     let mut buf = vec![0_u8; PATH_MAX];
-    let c_buf = buf.as_mut_ptr().cast();
 
-    let ret = unsafe { libc::ttyname_r(fd.as_fd().as_raw_fd(), c_buf, buf.len()) };
-    if ret != 0 {
-        return Err(Errno::from_raw(ret));
+    loop {
+        let c_buf = buf.as_mut_ptr().cast();
+        let ret = unsafe { libc::ttyname_r(fd.as_fd().as_raw_fd(), c_buf, buf.len()) };
+        match ret {
+            0 => break,
+            libc::ERANGE => buf.resize(buf.len() * 2, 0),
+            _ => return Err(Errno::from_raw(ret)),
+        }
     }
 
     CStr::from_bytes_until_nul(&buf[..])
         .map(|s| OsStr::from_bytes(s.to_bytes()).into())
         .map_err(|_| Errno::EINVAL)
 }
+
+/// Get the path of the controlling terminal of the calling process
+/// (see [`ctermid(3)`](https://pubs.opengroup.org/onlinepubs/9699919799/functions/ctermid.html)).
+///
+/// This is usually `/dev/tty`, which refers to the controlling terminal
+/// whatever it is, so opening it can still fail if the process has none.  The
+/// name is written to a buffer owned by this function rather than to
+/// `ctermid`'s static buffer, so this is thread-safe.
+#[cfg(any(
+    all(target_os = "linux", any(target_env = "gnu", target_env = "musl")),
+    target_os = "aix",
+    target_os = "haiku",
+    target_os = "hurd",
+))]
+pub fn ctermid() -> Result<PathBuf> {
+    // Larger than L_ctermid on any supported platform
+    let mut buf = [0 as c_char; 64];
+
+    let ret = unsafe { libc::ctermid(buf.as_mut_ptr()) };
+    // An empty string signals failure
+    if ret.is_null() || buf[0] == 0 {
+        return Err(Errno::ENOTTY);
+    }
+    let name = unsafe { CStr::from_ptr(buf.as_ptr()) };
+    Ok(OsStr::from_bytes(name.to_bytes()).into())
+}
+
+/// Simulate a hangup on the controlling terminal of the calling process
+/// (see [`vhangup(2)`](https://man7.org/linux/man-pages/man2/vhangup.2.html)).
+///
+/// Other processes that have the terminal open lose access to it, so that it
+/// can be handed over to a new session.  This needs the `CAP_SYS_TTY_CONFIG`
+/// capability, and fails with `EPERM` without it.
+#[cfg(target_os = "linux")]
+pub fn vhangup() -> Result<()> {
+    let res = unsafe { libc::vhangup() };
+
+    Errno::result(res).map(drop)
+}
 }
 
 feature! {
@@ -1,8 +1,66 @@
 use nix::errno::Errno;
-use nix::mount::{mount, MntFlags};
+use nix::mount::{mount, MntFlags, MountArg, MountError};
 
 #[test]
 fn test_mount() {
     let res = mount::<str, str, str>("", "", MntFlags::empty(), None);
-    assert_eq!(res, Err(Errno::ENOENT));
+    assert_eq!(res, Err(MountError::Mount(Errno::ENOENT)));
+}
+
+#[test]
+fn test_mount_source_with_nul() {
+    let res = mount::<[u8], str, str>(b"ap\0fs", "/", MntFlags::empty(), None);
+    assert_eq!(
+        res,
+        Err(MountError::InvalidPath(MountArg::Source, Errno::EINVAL))
+    );
+}
+
+#[test]
+fn test_mount_data_with_nul() {
+    let res = mount::<str, str, [u8]>(
+        "apfs",
+        "/",
+        MntFlags::empty(),
+        Some(b"da\0ta"),
+    );
+    assert_eq!(
+        res,
+        Err(MountError::InvalidPath(MountArg::Data, Errno::EINVAL))
+    );
+}
+
+#[test]
+fn test_getmntinfo() {
+    use nix::mount::{getmntinfo, MntWaitMode};
+    use std::path::Path;
+
+    let mounts = getmntinfo(MntWaitMode::MNT_NOWAIT).unwrap();
+    let root = mounts
+        .iter()
+        .find(|m| m.mounted_on() == Path::new("/"))
+        .expect("/ is not mounted");
+    assert!(!root.fstype().is_empty());
+    assert!(!root.mounted_from().as_os_str().is_empty());
+}
+
+#[test]
+fn test_fmount_source_with_nul() {
+    use nix::mount::fmount;
+
+    let dir = std::fs::File::open("/").unwrap();
+    let res = fmount::<_, [u8], str>(b"ap\0fs", &dir, MntFlags::empty(), None);
+    assert_eq!(
+        res,
+        Err(MountError::InvalidPath(MountArg::Source, Errno::EINVAL))
+    );
+}
+
+#[test]
+fn test_mnt_flags_values() {
+    use nix::mount::MntWaitMode;
+
+    assert_eq!(MntFlags::MNT_DOVOLFS.bits(), libc::MNT_DOVOLFS);
+    assert_eq!(MntWaitMode::MNT_WAIT as libc::c_int, libc::MNT_WAIT);
+    assert_eq!(MntWaitMode::MNT_NOWAIT as libc::c_int, libc::MNT_NOWAIT);
 }
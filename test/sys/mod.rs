@@ -12,6 +12,8 @@ mod test_signal;
     target_os = "netbsd"
 ))]
 mod test_aio;
+#[cfg(any(linux_android, target_os = "freebsd"))]
+mod test_eventfd;
 #[cfg(not(any(
     target_os = "redox",
     target_os = "fuchsia",
@@ -47,7 +49,13 @@ mod test_epoll;
 mod test_fanotify;
 #[cfg(target_os = "linux")]
 mod test_inotify;
+#[cfg(target_os = "linux")]
+mod test_membarrier;
 mod test_pthread;
+#[cfg(any(linux_android, apple_targets, freebsdlike, netbsdlike, solarish))]
+mod test_random;
+#[cfg(target_os = "linux")]
+mod test_userfaultfd;
 
 #[cfg(any(linux_android, freebsdlike, netbsdlike, apple_targets))]
 mod test_ptrace;
@@ -92,3 +100,6 @@ mod test_resource;
 // only enable this for FreeBSD for now.
 #[cfg(target_os = "freebsd")]
 mod test_memfd;
+
+#[cfg(any(linux_android, apple_targets))]
+mod test_xattr;
@@ -24,3 +24,32 @@ pub fn test_epoll_ctl() {
     epoll_ctl(efd, EpollOp::EpollCtlAdd, 1, &mut event).unwrap();
     epoll_ctl(efd, EpollOp::EpollCtlDel, 1, None).unwrap();
 }
+
+#[test]
+pub fn test_epoll_raw_fd_roundtrip() {
+    use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+    use nix::sys::epoll::Epoll;
+    use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+    let epoll = Epoll::new(EpollCreateFlags::EPOLL_CLOEXEC).unwrap();
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&epoll, FcntlArg::F_GETFD).unwrap());
+    assert!(flags.contains(FdFlag::FD_CLOEXEC));
+
+    let raw = epoll.into_raw_fd();
+    let epoll = unsafe { Epoll::from_raw_fd(raw) };
+    let mut events = [EpollEvent::empty()];
+    assert_eq!(epoll.wait(&mut events, 0u8).unwrap(), 0);
+}
+
+#[test]
+pub fn test_epoll_cloexec_flag() {
+    use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+    use nix::sys::epoll::Epoll;
+
+    // Only the flags passed in apply
+    let epoll = Epoll::new(EpollCreateFlags::empty()).unwrap();
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&epoll, FcntlArg::F_GETFD).unwrap());
+    assert!(!flags.contains(FdFlag::FD_CLOEXEC));
+}
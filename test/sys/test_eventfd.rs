@@ -0,0 +1,29 @@
+use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+use nix::sys::eventfd::{EfdFlags, EventFd};
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+#[test]
+fn test_eventfd_cloexec_by_default() {
+    let fd = EventFd::new().unwrap();
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&fd, FcntlArg::F_GETFD).unwrap());
+    assert!(flags.contains(FdFlag::FD_CLOEXEC));
+
+    let fd = EventFd::from_value(1).unwrap();
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&fd, FcntlArg::F_GETFD).unwrap());
+    assert!(flags.contains(FdFlag::FD_CLOEXEC));
+
+    let fd = EventFd::from_flags(EfdFlags::empty()).unwrap();
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&fd, FcntlArg::F_GETFD).unwrap());
+    assert!(!flags.contains(FdFlag::FD_CLOEXEC));
+}
+
+#[test]
+fn test_eventfd_raw_fd_roundtrip() {
+    let fd = EventFd::from_value(3).unwrap();
+    let raw = fd.into_raw_fd();
+    let fd = unsafe { EventFd::from_raw_fd(raw) };
+    assert_eq!(fd.read().unwrap(), 3);
+}
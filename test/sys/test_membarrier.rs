@@ -0,0 +1,34 @@
+use nix::errno::Errno;
+use nix::sys::membarrier::*;
+
+#[test]
+fn test_membarrier_query() {
+    let cmds = match membarrier_query() {
+        Err(Errno::ENOSYS) => {
+            skip!("membarrier is not available. Skipping test.")
+        }
+        res => res.unwrap(),
+    };
+    // Available on any kernel with CONFIG_MEMBARRIER and nohz_full off
+    assert!(cmds.contains(MembarrierCmdSet::MEMBARRIER_CMD_GLOBAL));
+
+    membarrier(MembarrierCmd::Global).unwrap();
+}
+
+#[test]
+fn test_membarrier_private_expedited() {
+    let cmds = match membarrier_query() {
+        Err(Errno::ENOSYS) => {
+            skip!("membarrier is not available. Skipping test.")
+        }
+        res => res.unwrap(),
+    };
+    if !cmds.contains(MembarrierCmdSet::MEMBARRIER_CMD_PRIVATE_EXPEDITED) {
+        skip!(
+            "MEMBARRIER_CMD_PRIVATE_EXPEDITED is not supported. Skipping test."
+        );
+    }
+
+    membarrier(MembarrierCmd::RegisterPrivateExpedited).unwrap();
+    membarrier(MembarrierCmd::PrivateExpedited).unwrap();
+}
@@ -198,3 +198,79 @@ fn test_madv_wipeonfork() {
         }
     }
 }
+
+#[test]
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+fn test_memfd_secret() {
+    use nix::errno::Errno;
+    use nix::sys::memfd::{memfd_secret, MFdSecretFlags};
+    use nix::sys::mman::{mmap, munmap};
+    use nix::unistd::{ftruncate, write};
+
+    let fd = match memfd_secret(MFdSecretFlags::O_CLOEXEC) {
+        Err(Errno::ENOSYS) => {
+            crate::skip!("memfd_secret is not supported. Skipping test.")
+        }
+        r => r.unwrap(),
+    };
+    // Secret memory can only be accessed through a mapping
+    assert!(write(&fd, b"key").is_err());
+
+    const LEN: usize = 4096;
+    ftruncate(&fd, LEN as i64).unwrap();
+    let mem = match unsafe {
+        mmap(
+            None,
+            NonZeroUsize::new(LEN).unwrap(),
+            ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+            MapFlags::MAP_SHARED,
+            &fd,
+            0,
+        )
+    } {
+        // Older kernels count secret memory against RLIMIT_MEMLOCK
+        Err(Errno::EAGAIN) => {
+            crate::skip!("RLIMIT_MEMLOCK is too low. Skipping test.")
+        }
+        r => r.unwrap(),
+    };
+    let slice: &mut [u8] =
+        unsafe { std::slice::from_raw_parts_mut(mem.as_ptr().cast(), LEN) };
+    slice[..3].copy_from_slice(b"key");
+    assert_eq!(&slice[..3], b"key");
+    unsafe { munmap(mem, LEN).unwrap() };
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_mbind() {
+    use nix::errno::Errno;
+    use nix::sys::mman::{mbind, munmap, MPol};
+
+    const LEN: usize = 4096;
+    let mem = unsafe {
+        mmap_anonymous(
+            None,
+            NonZeroUsize::new(LEN).unwrap(),
+            ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+            MapFlags::MAP_PRIVATE,
+        )
+        .unwrap()
+    };
+    let res = unsafe { mbind(mem, LEN, MPol::Bind, &[1]) };
+    match res {
+        Ok(()) => {
+            unsafe { mem.cast::<u8>().as_ptr().write(0xFF) };
+            unsafe { mbind(mem, LEN, MPol::Default, &[]) }.unwrap();
+        }
+        Err(Errno::ENOSYS) | Err(Errno::EPERM) => {
+            unsafe { munmap(mem, LEN) }.unwrap();
+            crate::skip!("mbind is not available. Skipping test.");
+        }
+        Err(e) => panic!("mbind failed: {e}"),
+    }
+    unsafe { munmap(mem, LEN) }.unwrap();
+}
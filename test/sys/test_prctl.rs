@@ -164,4 +164,107 @@ mod test_prctl {
         .unwrap_or_default();
         prctl::set_vma_anon_name(ptr, sz, None).unwrap_or_default();
     }
+
+    #[test]
+    fn test_get_seccomp() {
+        // The test runner may itself be confined by a filter, e.g. in a
+        // container.
+        let mode = prctl::get_seccomp().unwrap();
+        assert!(
+            mode == libc::SECCOMP_MODE_DISABLED
+                || mode == libc::SECCOMP_MODE_FILTER
+        );
+    }
+
+    #[test]
+    fn test_set_seccomp_strict() {
+        use nix::sys::signal::Signal;
+        use nix::sys::wait::{waitpid, WaitStatus};
+        use nix::unistd::{fork, pipe, read, write, ForkResult};
+
+        let (rd, wr) = pipe().unwrap();
+        match unsafe { fork() }.unwrap() {
+            ForkResult::Child => unsafe {
+                if prctl::set_seccomp_strict().is_err() {
+                    libc::_exit(1);
+                }
+                // write is allowed in strict mode, getpid is not.
+                let _ = write(&wr, b"x");
+                libc::syscall(libc::SYS_getpid);
+                libc::syscall(libc::SYS_exit, 0);
+                unreachable!()
+            },
+            ForkResult::Parent { child } => {
+                drop(wr);
+                let status = waitpid(child, None).unwrap();
+                assert_eq!(
+                    status,
+                    WaitStatus::Signaled(child, Signal::SIGKILL, false)
+                );
+                let mut buf = [0u8; 1];
+                assert_eq!(read(&rd, &mut buf).unwrap(), 1);
+                assert_eq!(&buf, b"x");
+            }
+        }
+    }
+
+    #[test]
+    fn test_seccomp_filter() {
+        use nix::sys::prctl::{SeccompFilterFlags, SeccompOperation};
+        use nix::sys::wait::{waitpid, WaitStatus};
+        use nix::unistd::{fork, ForkResult};
+
+        // Fail getppid with EPERM, allow everything else.
+        let filter = [
+            libc::sock_filter {
+                code: (libc::BPF_LD | libc::BPF_W | libc::BPF_ABS) as u16,
+                jt: 0,
+                jf: 0,
+                k: 0, // offsetof(struct seccomp_data, nr)
+            },
+            libc::sock_filter {
+                code: (libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K) as u16,
+                jt: 0,
+                jf: 1,
+                k: libc::SYS_getppid as u32,
+            },
+            libc::sock_filter {
+                code: (libc::BPF_RET | libc::BPF_K) as u16,
+                jt: 0,
+                jf: 0,
+                k: libc::SECCOMP_RET_ERRNO | libc::EPERM as u32,
+            },
+            libc::sock_filter {
+                code: (libc::BPF_RET | libc::BPF_K) as u16,
+                jt: 0,
+                jf: 0,
+                k: libc::SECCOMP_RET_ALLOW,
+            },
+        ];
+
+        match unsafe { fork() }.unwrap() {
+            ForkResult::Child => unsafe {
+                let loaded = prctl::set_no_new_privs().is_ok()
+                    && prctl::seccomp(
+                        SeccompOperation::SECCOMP_SET_MODE_FILTER(&filter),
+                        SeccompFilterFlags::empty(),
+                    )
+                    .is_ok();
+                let code = if !loaded {
+                    2
+                } else if libc::syscall(libc::SYS_getppid) == -1
+                    && nix::errno::Errno::last() == nix::errno::Errno::EPERM
+                {
+                    0
+                } else {
+                    1
+                };
+                libc::_exit(code)
+            },
+            ForkResult::Parent { child } => {
+                let status = waitpid(child, None).unwrap();
+                assert_eq!(status, WaitStatus::Exited(child, 0));
+            }
+        }
+    }
 }
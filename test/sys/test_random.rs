@@ -0,0 +1,20 @@
+use nix::sys::random::{default_source, RandomSource};
+
+#[test]
+fn test_default_source_fill() {
+    // Larger than the 256 byte limit of getentropy, to exercise chunking.
+    let mut buf = [0u8; 1024];
+    default_source().fill(&mut buf).unwrap();
+    // The odds of 1024 random bytes all being zero are negligible.
+    assert!(buf.iter().any(|&b| b != 0));
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+#[test]
+fn test_getentropy_source_fill() {
+    use nix::sys::random::GetEntropy;
+
+    let mut buf = [0u8; 1024];
+    GetEntropy.fill(&mut buf).unwrap();
+    assert!(buf.iter().any(|&b| b != 0));
+}
@@ -214,6 +214,47 @@ fn fdset_fds() {
     );
 }
 
+#[test]
+fn fdset_try_from_iter() {
+    let raw_fds: Vec<RawFd> = vec![9, 2, 40];
+    let fds: Vec<_> = raw_fds
+        .iter()
+        .map(|&fd| unsafe { BorrowedFd::borrow_raw(fd) })
+        .collect();
+
+    let set = FdSet::try_from_iter(fds.iter().copied()).unwrap();
+    assert_eq!(
+        set.fds(None)
+            .map(|borrowed_fd| borrowed_fd.as_raw_fd())
+            .collect::<Vec<_>>(),
+        vec![2, 9, 40]
+    );
+    assert_eq!(set.highest().map(|fd| fd.as_raw_fd()), Some(40));
+
+    let mut set2 = FdSet::new();
+    set2.insert_all(&fds).unwrap();
+    assert_eq!(set, set2);
+}
+
+#[test]
+fn fdset_too_large_fd_is_an_error() {
+    let fd_ok = unsafe { BorrowedFd::borrow_raw(3) };
+    let fd_too_large = unsafe { BorrowedFd::borrow_raw(FD_SETSIZE as RawFd) };
+
+    assert_eq!(
+        FdSet::try_from_iter([fd_ok, fd_too_large]),
+        Err(nix::errno::Errno::EINVAL)
+    );
+
+    let mut set = FdSet::new();
+    assert_eq!(
+        set.insert_all(&[fd_ok, fd_too_large]),
+        Err(nix::errno::Errno::EINVAL)
+    );
+    // Nothing was inserted
+    assert!(set.highest().is_none());
+}
+
 #[test]
 fn test_select() {
     let (r1, w1) = pipe().unwrap();
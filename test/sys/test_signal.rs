@@ -165,6 +165,32 @@ fn test_clear() {
     }
 }
 
+#[test]
+fn test_iterator_covers_all() {
+    let all = SigSet::all();
+    let signals: Vec<Signal> = Signal::iterator().collect();
+    for (i, signal) in signals.iter().enumerate() {
+        assert!(all.contains(*signal));
+        assert!(!signals[..i].contains(signal));
+    }
+    assert!(signals.contains(&SIGKILL));
+    assert!(signals.contains(&SIGSTOP));
+}
+
+#[test]
+#[cfg(not(target_os = "redox"))]
+fn test_sigaction_uncatchable() {
+    extern "C" fn handler(_: libc::c_int) {}
+    let action = SigAction::new(
+        SigHandler::Handler(handler),
+        SaFlags::empty(),
+        SigSet::empty(),
+    );
+    for signal in [SIGKILL, SIGSTOP] {
+        assert_eq!(unsafe { sigaction(signal, &action) }, Err(Errno::EINVAL));
+    }
+}
+
 #[test]
 fn test_from_str_round_trips() {
     for signal in Signal::iterator() {
@@ -173,12 +199,34 @@ fn test_from_str_round_trips() {
     }
 }
 
+#[test]
+fn test_from_str_short_names() {
+    assert_eq!("TERM".parse::<Signal>(), Ok(SIGTERM));
+    assert_eq!("kill".parse::<Signal>(), Ok(SIGKILL));
+    assert_eq!("SigHup".parse::<Signal>(), Ok(SIGHUP));
+    assert_eq!("sigusr1".parse::<Signal>(), Ok(SIGUSR1));
+    for signal in Signal::iterator() {
+        assert_eq!(signal.as_str()[3..].parse::<Signal>(), Ok(signal));
+    }
+}
+
+#[test]
+fn test_from_str_numbers() {
+    assert_eq!("9".parse::<Signal>(), Ok(SIGKILL));
+    assert_eq!("15".parse::<Signal>(), Ok(SIGTERM));
+    for signal in Signal::iterator() {
+        assert_eq!((signal as i32).to_string().parse::<Signal>(), Ok(signal));
+    }
+}
+
 #[test]
 fn test_from_str_invalid_value() {
     let errval = Err(Errno::EINVAL);
     assert_eq!("NOSIGNAL".parse::<Signal>(), errval);
-    assert_eq!("kill".parse::<Signal>(), errval);
-    assert_eq!("9".parse::<Signal>(), errval);
+    assert_eq!("SIG".parse::<Signal>(), errval);
+    assert_eq!("".parse::<Signal>(), errval);
+    assert_eq!("0".parse::<Signal>(), errval);
+    assert_eq!("-9".parse::<Signal>(), errval);
 }
 
 #[test]
@@ -327,6 +375,54 @@ fn test_sigaction() {
     .unwrap();
 }
 
+#[test]
+#[cfg(not(target_os = "redox"))]
+fn test_sig_mask_guard() {
+    thread::spawn(|| {
+        let sigint = SigSet::from(SIGINT);
+        let before = SigSet::thread_get_mask().unwrap();
+        assert!(!before.contains(SIGINT));
+        {
+            let guard =
+                SigMaskGuard::new(SigmaskHow::SIG_BLOCK, &sigint).unwrap();
+            assert_eq!(*guard.oldmask(), before);
+            assert!(SigSet::thread_get_mask().unwrap().contains(SIGINT));
+
+            raise(SIGINT).unwrap();
+            assert!(sigpending().unwrap().contains(SIGINT));
+            // Consume it before the mask is restored
+            assert_eq!(sigwait(&sigint).unwrap(), SIGINT);
+        }
+        assert_eq!(SigSet::thread_get_mask().unwrap(), before);
+
+        let old = sigprocmask_swap(SigmaskHow::SIG_BLOCK, &sigint).unwrap();
+        assert_eq!(old, before);
+        let old = sigprocmask_swap(SigmaskHow::SIG_SETMASK, &before).unwrap();
+        assert!(old.contains(SIGINT));
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn test_block_scoped_panic() {
+    thread::spawn(|| {
+        let sigusr1 = SigSet::from(SIGUSR1);
+        let before = SigSet::thread_get_mask().unwrap();
+        assert!(!before.contains(SIGUSR1));
+
+        let res = std::panic::catch_unwind(|| {
+            let _guard = sigusr1.block_scoped().unwrap();
+            assert!(SigSet::thread_get_mask().unwrap().contains(SIGUSR1));
+            panic!("in the critical section");
+        });
+        assert!(res.is_err());
+        assert_eq!(SigSet::thread_get_mask().unwrap(), before);
+    })
+    .join()
+    .unwrap();
+}
+
 #[test]
 #[cfg(not(target_os = "redox"))]
 fn test_sigwait() {
@@ -343,6 +439,21 @@ fn test_sigwait() {
     .unwrap();
 }
 
+#[test]
+#[cfg(not(target_os = "redox"))]
+fn test_sigwait_fn() {
+    thread::spawn(|| {
+        let mut mask = SigSet::empty();
+        mask.add(SIGUSR2);
+        mask.thread_block().unwrap();
+
+        raise(SIGUSR2).unwrap();
+        assert_eq!(sigwait(&mask).unwrap(), SIGUSR2);
+    })
+    .join()
+    .unwrap();
+}
+
 #[cfg(any(
     bsd,
     linux_android,
@@ -8,6 +8,39 @@ fn create_signalfd() {
     SignalFd::new(&mask).unwrap();
 }
 
+#[test]
+fn create_signalfd_cloexec_by_default() {
+    use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+    use nix::sys::{signal::SigSet, signalfd::SignalFd};
+
+    let mask = SigSet::empty();
+    let fd = SignalFd::new(&mask).unwrap();
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&fd, FcntlArg::F_GETFD).unwrap());
+    assert!(flags.contains(FdFlag::FD_CLOEXEC));
+}
+
+#[test]
+fn sfd_flags_match_libc() {
+    use nix::fcntl::{fcntl, FcntlArg, OFlag};
+    use nix::sys::{
+        signal::SigSet,
+        signalfd::{SfdFlags, SignalFd},
+    };
+
+    assert_eq!(SfdFlags::SFD_NONBLOCK.bits(), libc::SFD_NONBLOCK);
+    assert_eq!(SfdFlags::SFD_CLOEXEC.bits(), libc::SFD_CLOEXEC);
+    assert_eq!(
+        SfdFlags::from_bits(libc::SFD_NONBLOCK | libc::SFD_CLOEXEC),
+        Some(SfdFlags::SFD_NONBLOCK | SfdFlags::SFD_CLOEXEC)
+    );
+
+    let mask = SigSet::empty();
+    let fd = SignalFd::with_flags(&mask, SfdFlags::SFD_NONBLOCK).unwrap();
+    let flags = OFlag::from_bits_retain(fcntl(&fd, FcntlArg::F_GETFL).unwrap());
+    assert!(flags.contains(OFlag::O_NONBLOCK));
+}
+
 #[test]
 fn create_signalfd_with_opts() {
     use nix::sys::{
@@ -88,3 +121,203 @@ fn test_signalfd_setmask() {
     let signo = Signal::try_from(res.ssi_signo as i32).unwrap();
     assert_eq!(signo, signal::SIGUSR1);
 }
+
+/// Poll a non-blocking signalfd for readiness, then read from it.
+#[test]
+#[cfg(feature = "poll")]
+fn test_signalfd_poll() {
+    use nix::poll::{poll, PollFd, PollFlags, PollTimeout};
+    use nix::sys::signal::{self, raise, SigSet, Signal};
+    use nix::sys::signalfd::{SfdFlags, SignalFd};
+    use std::os::unix::io::AsFd;
+
+    // Grab the mutex for altering signals so we don't interfere with other tests.
+    let _m = crate::SIGNAL_MTX.lock();
+
+    let mut mask = SigSet::empty();
+    mask.add(signal::SIGUSR1);
+    mask.thread_block().unwrap();
+
+    let fd = SignalFd::with_flags(&mask, SfdFlags::SFD_NONBLOCK).unwrap();
+
+    let mut fds = [PollFd::new(fd.as_fd(), PollFlags::POLLIN)];
+    assert_eq!(poll(&mut fds, PollTimeout::ZERO).unwrap(), 0);
+    assert!(fd.read_signal().unwrap().is_none());
+
+    raise(signal::SIGUSR1).expect("Error: raise(SIGUSR1) failed");
+
+    let mut fds = [PollFd::new(fd.as_fd(), PollFlags::POLLIN)];
+    assert_eq!(poll(&mut fds, PollTimeout::ZERO).unwrap(), 1);
+    assert!(fds[0].revents().unwrap().contains(PollFlags::POLLIN));
+    let res = fd.read_signal().unwrap().unwrap();
+    let signo = Signal::try_from(res.ssi_signo as i32).unwrap();
+    assert_eq!(signo, signal::SIGUSR1);
+
+    // The signal has been consumed, so a further read would block.
+    assert!(fd.read_signal().unwrap().is_none());
+}
+
+#[test]
+fn test_siginfo_signal() {
+    use nix::sys::signal::{self, raise, SigSet};
+    use nix::sys::signalfd::{siginfo, SfdFlags, SiginfoExt, SignalFd};
+
+    // Grab the mutex for altering signals so we don't interfere with other tests.
+    let _m = crate::SIGNAL_MTX.lock();
+
+    let mut mask = SigSet::empty();
+    mask.add(signal::SIGUSR1);
+    mask.thread_block().unwrap();
+
+    let mut fd = SignalFd::with_flags(&mask, SfdFlags::SFD_NONBLOCK).unwrap();
+    raise(signal::SIGUSR1).expect("Error: raise(SIGUSR1) failed");
+    let info = fd.next().unwrap().unwrap();
+    assert_eq!(info.signal(), Some(signal::SIGUSR1));
+
+    let mut info: siginfo = unsafe { std::mem::zeroed() };
+    info.ssi_signo = 0;
+    assert_eq!(info.signal(), None);
+    #[cfg(target_os = "linux")]
+    {
+        info.ssi_signo = libc::SIGRTMIN() as u32;
+        assert_eq!(info.signal(), None);
+    }
+}
+
+#[test]
+fn test_signalfd_iter() {
+    use nix::errno::Errno;
+    use nix::sys::signal::{self, raise, SigSet};
+    use nix::sys::signalfd::{SfdFlags, SiginfoExt, SignalFd};
+    use nix::unistd::pipe;
+    use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+    // Grab the mutex for altering signals so we don't interfere with other tests.
+    let _m = crate::SIGNAL_MTX.lock();
+
+    let mut mask = SigSet::empty();
+    mask.add(signal::SIGUSR1);
+    mask.thread_block().unwrap();
+
+    // A drained queue ends the iteration.
+    let mut fd = SignalFd::with_flags(&mask, SfdFlags::SFD_NONBLOCK).unwrap();
+    raise(signal::SIGUSR1).expect("Error: raise(SIGUSR1) failed");
+    let signals = fd
+        .by_ref()
+        .map(|info| info.unwrap().signal())
+        .collect::<Vec<_>>();
+    assert_eq!(signals, [Some(signal::SIGUSR1)]);
+    assert!(fd.next().is_none());
+
+    // A read error is yielded, not mistaken for an empty queue.
+    let (_r, w) = pipe().unwrap();
+    let mut fd = unsafe { SignalFd::from_raw_fd(w.into_raw_fd()) };
+    assert_eq!(fd.next().map(|r| r.map(drop)), Some(Err(Errno::EBADF)));
+}
+
+#[test]
+fn test_signalfd_read_signal_timeout() {
+    use nix::sys::signal::{self, raise, SigSet, Signal};
+    use nix::sys::signalfd::SignalFd;
+    use nix::sys::time::{TimeSpec, TimeValLike};
+    use std::time::{Duration, Instant};
+
+    // Grab the mutex for altering signals so we don't interfere with other tests.
+    let _m = crate::SIGNAL_MTX.lock();
+
+    let mut mask = SigSet::empty();
+    mask.add(signal::SIGUSR2);
+    mask.thread_block().unwrap();
+
+    let fd = SignalFd::new(&mask).unwrap();
+
+    let start = Instant::now();
+    let res = fd.read_signal_timeout(TimeSpec::milliseconds(50)).unwrap();
+    assert!(res.is_none());
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    raise(signal::SIGUSR2).expect("Error: raise(SIGUSR2) failed");
+
+    let res = fd
+        .read_signal_timeout(TimeSpec::seconds(10))
+        .unwrap()
+        .unwrap();
+    let signo = Signal::try_from(res.ssi_signo as i32).unwrap();
+    assert_eq!(signo, signal::SIGUSR2);
+}
+
+#[test]
+fn test_signalfd_read_signals() {
+    use nix::sys::signal::{self, raise, SigSet, Signal};
+    use nix::sys::signalfd::{siginfo, SfdFlags, SignalFd};
+
+    // Grab the mutex for altering signals so we don't interfere with other tests.
+    let _m = crate::SIGNAL_MTX.lock();
+
+    let mut mask = SigSet::empty();
+    mask.add(signal::SIGUSR1);
+    mask.add(signal::SIGUSR2);
+    mask.thread_block().unwrap();
+
+    let fd = SignalFd::with_flags(&mask, SfdFlags::SFD_NONBLOCK).unwrap();
+
+    raise(signal::SIGUSR1).expect("Error: raise(SIGUSR1) failed");
+    raise(signal::SIGUSR2).expect("Error: raise(SIGUSR2) failed");
+
+    let mut buf: [siginfo; 4] = unsafe { std::mem::zeroed() };
+    assert_eq!(fd.read_signals(&mut buf), Ok(2));
+    let mut signos: Vec<_> = buf[..2]
+        .iter()
+        .map(|si| Signal::try_from(si.ssi_signo as i32).unwrap())
+        .collect();
+    signos.sort();
+    assert_eq!(signos, [signal::SIGUSR1, signal::SIGUSR2]);
+
+    // The queue is drained
+    assert_eq!(fd.read_signals(&mut buf), Ok(0));
+}
+
+#[test]
+fn test_signalfd_drain_children() {
+    use nix::sys::signal::{SigSet, SIGCHLD};
+    use nix::sys::signalfd::SignalFd;
+    use nix::sys::wait::WaitStatus;
+    use nix::unistd::{fork, ForkResult};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let _m = crate::FORK_MTX.lock();
+    let _s = crate::SIGNAL_MTX.lock();
+
+    let mut mask = SigSet::empty();
+    mask.add(SIGCHLD);
+    mask.thread_block().unwrap();
+    let mut fd = SignalFd::new(&mask).unwrap();
+
+    let mut children = Vec::new();
+    for code in [3, 4] {
+        // Safe: The child only calls `_exit`, which is async-signal-safe.
+        match unsafe { fork() }.expect("Error: Fork Failed") {
+            ForkResult::Child => unsafe { libc::_exit(code) },
+            ForkResult::Parent { child } => children.push((child, code)),
+        }
+    }
+
+    // SIGCHLD is directed at the whole process, so it may be handled by some
+    // other test thread instead of showing up on our signalfd.  Poll instead.
+    let mut reaped = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while reaped.len() < children.len() && Instant::now() < deadline {
+        reaped.extend(fd.drain_children().unwrap());
+        thread::sleep(Duration::from_millis(10));
+    }
+    mask.thread_unblock().unwrap();
+
+    reaped.sort_by_key(|&(pid, _)| pid);
+    let expected: Vec<_> = children
+        .iter()
+        .map(|&(pid, code)| (pid, WaitStatus::Exited(pid, code)))
+        .collect();
+    assert_eq!(reaped, expected);
+    assert!(fd.drain_children().unwrap().is_empty());
+}
@@ -326,6 +326,96 @@ pub fn test_socketpair() {
     assert_eq!(&buf[..], b"hello");
 }
 
+// On Apple targets, this exercises the fcntl emulation of the flags.
+#[test]
+pub fn test_socket_cloexec_nonblock() {
+    use nix::fcntl::{fcntl, FcntlArg, FdFlag, OFlag};
+    use nix::sys::socket::{socket, AddressFamily, SockFlag, SockType};
+
+    let fd = socket(
+        AddressFamily::Unix,
+        SockType::Stream,
+        SockFlag::SOCK_CLOEXEC | SockFlag::SOCK_NONBLOCK,
+        None,
+    )
+    .unwrap();
+    let fdflags =
+        FdFlag::from_bits_truncate(fcntl(&fd, FcntlArg::F_GETFD).unwrap());
+    assert!(fdflags.contains(FdFlag::FD_CLOEXEC));
+    let oflags =
+        OFlag::from_bits_truncate(fcntl(&fd, FcntlArg::F_GETFL).unwrap());
+    assert!(oflags.contains(OFlag::O_NONBLOCK));
+
+    let fd = socket(
+        AddressFamily::Unix,
+        SockType::Stream,
+        SockFlag::empty(),
+        None,
+    )
+    .unwrap();
+    let fdflags =
+        FdFlag::from_bits_truncate(fcntl(&fd, FcntlArg::F_GETFD).unwrap());
+    assert!(!fdflags.contains(FdFlag::FD_CLOEXEC));
+    let oflags =
+        OFlag::from_bits_truncate(fcntl(&fd, FcntlArg::F_GETFL).unwrap());
+    assert!(!oflags.contains(OFlag::O_NONBLOCK));
+}
+
+#[test]
+pub fn test_socketpair_cloexec() {
+    use nix::fcntl::{fcntl, FcntlArg, FdFlag, OFlag};
+    use nix::sys::socket::{socketpair, AddressFamily, SockFlag, SockType};
+
+    let (fd1, fd2) = socketpair(
+        AddressFamily::Unix,
+        SockType::Stream,
+        None,
+        SockFlag::SOCK_CLOEXEC,
+    )
+    .unwrap();
+    for fd in [&fd1, &fd2] {
+        let fdflags =
+            FdFlag::from_bits_truncate(fcntl(fd, FcntlArg::F_GETFD).unwrap());
+        assert!(fdflags.contains(FdFlag::FD_CLOEXEC));
+        let oflags =
+            OFlag::from_bits_truncate(fcntl(fd, FcntlArg::F_GETFL).unwrap());
+        assert!(!oflags.contains(OFlag::O_NONBLOCK));
+    }
+}
+
+#[test]
+pub fn test_tcp_urgent_data() {
+    use nix::sys::socket::{recv, send, setsockopt, sockopt, MsgFlags};
+    use std::net::{TcpListener, TcpStream};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Urgent data is read out of band
+    let client = TcpStream::connect(addr).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    // With MSG_OOB, the last byte sent is the urgent one
+    send(client.as_raw_fd(), b"ab!", MsgFlags::MSG_OOB).unwrap();
+    let mut buf = [0u8; 3];
+    // A normal read stops at the urgent data mark
+    let n = recv(server.as_raw_fd(), &mut buf, MsgFlags::empty()).unwrap();
+    assert_eq!(&buf[..n], b"ab");
+    let n = recv(server.as_raw_fd(), &mut buf, MsgFlags::MSG_OOB).unwrap();
+    assert_eq!(&buf[..n], b"!");
+
+    // With SO_OOBINLINE, urgent data is part of the normal stream
+    let client = TcpStream::connect(addr).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    setsockopt(&server, sockopt::OobInline, &true).unwrap();
+    send(client.as_raw_fd(), b"ab!", MsgFlags::MSG_OOB).unwrap();
+    let mut l = 0;
+    while l < buf.len() {
+        l +=
+            recv(server.as_raw_fd(), &mut buf[l..], MsgFlags::empty()).unwrap();
+    }
+    assert_eq!(&buf, b"ab!");
+}
+
 #[test]
 pub fn test_recvmsg_sockaddr_un() {
     use nix::sys::socket::{
@@ -461,6 +551,134 @@ mod recvfrom {
         assert_eq!(AddressFamily::Inet, from.unwrap().family().unwrap());
     }
 
+    #[cfg(linux_android)]
+    #[test]
+    pub fn udp_trunc() {
+        let any =
+            SockaddrIn::from(SocketAddrV4::from_str("127.0.0.1:0").unwrap());
+        let rsock = socket(
+            AddressFamily::Inet,
+            SockType::Datagram,
+            SockFlag::empty(),
+            None,
+        )
+        .unwrap();
+        bind(rsock.as_raw_fd(), &any).unwrap();
+        let sock_addr: SockaddrIn = getsockname(rsock.as_raw_fd()).unwrap();
+        let ssock = socket(
+            AddressFamily::Inet,
+            SockType::Datagram,
+            SockFlag::empty(),
+            None,
+        )
+        .unwrap();
+        sendto(ssock.as_raw_fd(), MSG, &sock_addr, MsgFlags::empty()).unwrap();
+
+        // Peeking with a short buffer reports the full datagram length
+        let mut small = [0u8; 4];
+        let (len, from) = recvfrom_with_flags::<SockaddrStorage>(
+            rsock.as_raw_fd(),
+            &mut small,
+            MsgFlags::MSG_PEEK | MsgFlags::MSG_TRUNC,
+        )
+        .unwrap();
+        assert_eq!(len, MSG.len());
+        assert_eq!(&small, &MSG[..4]);
+        assert!(from.is_some());
+
+        // The datagram is still queued and can be read in full
+        let mut buf = vec![0u8; len];
+        let (len, _) =
+            recvfrom::<SockaddrStorage>(rsock.as_raw_fd(), &mut buf).unwrap();
+        assert_eq!(&buf[..len], MSG);
+
+        // Without MSG_PEEK the real length is still reported, but the
+        // datagram is consumed
+        sendto(ssock.as_raw_fd(), MSG, &sock_addr, MsgFlags::empty()).unwrap();
+        let len =
+            recv(rsock.as_raw_fd(), &mut small, MsgFlags::MSG_TRUNC).unwrap();
+        assert_eq!(len, MSG.len());
+        assert_eq!(
+            recv(rsock.as_raw_fd(), &mut small, MsgFlags::MSG_DONTWAIT),
+            Err(Errno::EAGAIN)
+        );
+    }
+
+    #[test]
+    // OpenBSD doesn't support IPv4-mapped addresses
+    #[cfg(not(target_os = "openbsd"))]
+    pub fn dual_stack() {
+        use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV6};
+
+        let rsock = socket(
+            AddressFamily::Inet6,
+            SockType::Datagram,
+            SockFlag::empty(),
+            None,
+        )
+        .unwrap();
+        setsockopt(&rsock, sockopt::Ipv6V6Only, &false).unwrap();
+        let any = SockaddrIn6::from(SocketAddrV6::new(
+            Ipv6Addr::UNSPECIFIED,
+            0,
+            0,
+            0,
+        ));
+        bind(rsock.as_raw_fd(), &any).unwrap();
+        let port = getsockname::<SockaddrIn6>(rsock.as_raw_fd())
+            .unwrap()
+            .port();
+        let mut buf = [0u8; 13];
+
+        // An IPv4 peer shows up as an IPv4-mapped address
+        let ssock4 = socket(
+            AddressFamily::Inet,
+            SockType::Datagram,
+            SockFlag::empty(),
+            None,
+        )
+        .unwrap();
+        let dst = SockaddrIn::new(127, 0, 0, 1, port);
+        sendto(ssock4.as_raw_fd(), MSG, &dst, MsgFlags::empty()).unwrap();
+        let (len, from) =
+            recvfrom::<SockaddrStorage>(rsock.as_raw_fd(), &mut buf).unwrap();
+        assert_eq!(&buf[..len], MSG);
+        let from = from.unwrap();
+        assert_eq!(from.family(), Some(AddressFamily::Inet6));
+        assert_eq!(
+            from.as_sockaddr_in6().unwrap().ip(),
+            Ipv4Addr::LOCALHOST.to_ipv6_mapped()
+        );
+
+        // A native IPv6 peer
+        let ssock6 = socket(
+            AddressFamily::Inet6,
+            SockType::Datagram,
+            SockFlag::empty(),
+            None,
+        )
+        .unwrap();
+        let dst = SockaddrIn6::from(SocketAddrV6::new(
+            Ipv6Addr::LOCALHOST,
+            port,
+            0,
+            0,
+        ));
+        match sendto(ssock6.as_raw_fd(), MSG, &dst, MsgFlags::empty()) {
+            Err(Errno::EADDRNOTAVAIL) | Err(Errno::ENETUNREACH) => {
+                println!("IPv6 not available, skipping test.");
+                return;
+            }
+            res => res.unwrap(),
+        };
+        let (len, from) =
+            recvfrom::<SockaddrStorage>(rsock.as_raw_fd(), &mut buf).unwrap();
+        assert_eq!(&buf[..len], MSG);
+        let from = from.unwrap();
+        assert_eq!(from.family(), Some(AddressFamily::Inet6));
+        assert_eq!(from.as_sockaddr_in6().unwrap().ip(), Ipv6Addr::LOCALHOST);
+    }
+
     #[cfg(target_os = "linux")]
     mod udp_offload {
         use super::*;
@@ -1379,6 +1597,186 @@ fn test_scm_rights_single_cmsg_multiple_fds() {
     thread.join().unwrap();
 }
 
+#[cfg_attr(qemu, ignore)]
+#[test]
+fn test_scm_rights_multiple_fds() {
+    use nix::errno::Errno;
+    use nix::sys::socket::{
+        recvmsg, sendmsg, socketpair, AddressFamily, ControlMessage,
+        ControlMessageOwned, MsgFlags, SockFlag, SockType,
+    };
+    use nix::unistd::{pipe, read, write};
+    use std::io::{IoSlice, IoSliceMut};
+    use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+
+    let (send, receive) = socketpair(
+        AddressFamily::Unix,
+        SockType::Datagram,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    let pipes = [pipe().unwrap(), pipe().unwrap(), pipe().unwrap()];
+
+    // Send the write ends of all the pipes in one message, and the first one
+    // again in a second message.
+    let fds: Vec<RawFd> = pipes.iter().map(|(_, w)| w.as_raw_fd()).collect();
+    let first = [fds[0]];
+    let cmsgs = [
+        ControlMessage::ScmRights(&fds),
+        ControlMessage::ScmRights(&first),
+    ];
+    let iov = [IoSlice::new(b"x")];
+    match sendmsg::<()>(send.as_raw_fd(), &iov, &cmsgs, MsgFlags::empty(), None)
+    {
+        // Some platforms only accept a single SCM_RIGHTS message
+        Err(Errno::EINVAL) => sendmsg::<()>(
+            send.as_raw_fd(),
+            &iov,
+            &cmsgs[..1],
+            MsgFlags::empty(),
+            None,
+        )
+        .unwrap(),
+        r => r.unwrap(),
+    };
+
+    let mut buf = [0u8; 1];
+    let mut iov = [IoSliceMut::new(&mut buf)];
+    let mut space = cmsg_space!([RawFd; 3], RawFd);
+    let msg = recvmsg::<()>(
+        receive.as_raw_fd(),
+        &mut iov,
+        Some(&mut space),
+        MsgFlags::empty(),
+    )
+    .unwrap();
+    let mut received = Vec::new();
+    for cmsg in msg.cmsgs().unwrap() {
+        if let ControlMessageOwned::ScmRights(fds) = cmsg {
+            received.extend(
+                fds.into_iter()
+                    .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) }),
+            );
+        }
+    }
+    assert!(received.len() == 3 || received.len() == 4);
+
+    for (i, fd) in received.iter().enumerate() {
+        let (r, _) = &pipes[i % 3];
+        write(fd, &[i as u8]).unwrap();
+        let mut b = [0u8; 1];
+        read(r, &mut b).unwrap();
+        assert_eq!(b[0], i as u8);
+    }
+}
+
+// If the control buffer is too small, the descriptors that were received
+// anyway must be closed.
+#[cfg(linux_android)]
+#[cfg_attr(qemu, ignore)]
+#[test]
+fn test_scm_rights_truncated() {
+    use nix::errno::Errno;
+    use nix::fcntl::{fcntl, FcntlArg, OFlag};
+    use nix::sys::socket::{
+        recvmsg, sendmsg, socketpair, AddressFamily, ControlMessage, MsgFlags,
+        SockFlag, SockType,
+    };
+    use nix::unistd::{pipe, read, write};
+    use std::io::{IoSlice, IoSliceMut};
+    use std::os::unix::io::{AsRawFd, RawFd};
+
+    let (send, receive) = socketpair(
+        AddressFamily::Unix,
+        SockType::Datagram,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    let (r, w) = pipe().unwrap();
+    fcntl(&r, FcntlArg::F_SETFL(OFlag::O_NONBLOCK)).unwrap();
+
+    let fds = [w.as_raw_fd(); 4];
+    let cmsg = [ControlMessage::ScmRights(&fds)];
+    let iov = [IoSlice::new(b"x")];
+    sendmsg::<()>(send.as_raw_fd(), &iov, &cmsg, MsgFlags::empty(), None)
+        .unwrap();
+    drop(w);
+
+    let mut buf = [0u8; 1];
+    let mut iov = [IoSliceMut::new(&mut buf)];
+    let mut space = cmsg_space!(RawFd);
+    let msg = recvmsg::<()>(
+        receive.as_raw_fd(),
+        &mut iov,
+        Some(&mut space),
+        MsgFlags::empty(),
+    )
+    .unwrap();
+    assert!(msg.flags.contains(MsgFlags::MSG_CTRUNC));
+    assert_eq!(msg.cmsgs().unwrap_err(), Errno::ENOBUFS);
+
+    // Every write end is closed, so the pipe reports end of file
+    let mut b = [0u8; 1];
+    assert_eq!(read(&r, &mut b), Ok(0));
+
+    // Asking again must not close the same descriptor numbers, which may
+    // already have been reused.
+    let (r2, w2) = pipe().unwrap();
+    assert_eq!(msg.cmsgs().unwrap_err(), Errno::ENOBUFS);
+    write(&w2, b"x").unwrap();
+    read(&r2, &mut b).unwrap();
+    assert_eq!(&b, b"x");
+}
+
+#[cfg_attr(qemu, ignore)]
+#[test]
+fn test_recvmsg_with_fds() {
+    use nix::sys::socket::{
+        recvmsg_with_fds, sendmsg, socketpair, AddressFamily, ControlMessage,
+        MsgFlags, SockFlag, SockType,
+    };
+    use nix::unistd::{pipe, read, write};
+    use std::io::{IoSlice, IoSliceMut};
+    use std::os::unix::io::{AsRawFd, RawFd};
+
+    let (send, receive) = socketpair(
+        AddressFamily::Unix,
+        SockType::Stream,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    let (r, w) = pipe().unwrap();
+
+    let fds = [w.as_raw_fd()];
+    let cmsg = [ControlMessage::ScmRights(&fds)];
+    let iov = [IoSlice::new(b"hello")];
+    sendmsg::<()>(send.as_raw_fd(), &iov, &cmsg, MsgFlags::empty(), None)
+        .unwrap();
+    drop(w);
+
+    let mut buf = [0u8; 5];
+    let mut iov = [IoSliceMut::new(&mut buf)];
+    let mut space = cmsg_space!(RawFd);
+    let (bytes, _, received) = recvmsg_with_fds(
+        receive.as_raw_fd(),
+        &mut iov,
+        &mut space,
+        MsgFlags::empty(),
+    )
+    .unwrap();
+    assert_eq!(bytes, 5);
+    assert_eq!(&buf, b"hello");
+    assert_eq!(received.len(), 1);
+
+    write(&received[0], b"x").unwrap();
+    let mut b = [0u8; 1];
+    read(&r, &mut b).unwrap();
+    assert_eq!(&b, b"x");
+}
+
 // Verify `sendmsg` builds a valid `msghdr` when passing an empty
 // `cmsgs` argument.  This should result in a msghdr with a nullptr
 // msg_control field and a msg_controllen of 0 when calling into the
@@ -1758,6 +2156,43 @@ pub fn test_unnamed_unixdomain_autobind() {
     assert_eq!(addr.len(), 5);
 }
 
+// An autobound datagram socket can be reached through its abstract name
+#[cfg(linux_android)]
+#[test]
+pub fn test_unnamed_unixdomain_autobind_datagram() {
+    use nix::sys::socket::{bind, getsockname, recv, sendto, socket};
+    use nix::sys::socket::{MsgFlags, SockFlag, SockType};
+
+    let rx = socket(
+        AddressFamily::Unix,
+        SockType::Datagram,
+        SockFlag::empty(),
+        None,
+    )
+    .expect("socket failed");
+    bind(rx.as_raw_fd(), &UnixAddr::new_unnamed()).expect("bind failed");
+
+    let addr: UnixAddr =
+        getsockname(rx.as_raw_fd()).expect("getsockname failed");
+    let name = addr.as_abstract().unwrap();
+    assert_eq!(name.len(), 5);
+    assert!(name.iter().all(u8::is_ascii_hexdigit));
+
+    let tx = socket(
+        AddressFamily::Unix,
+        SockType::Datagram,
+        SockFlag::empty(),
+        None,
+    )
+    .expect("socket failed");
+    let dest = UnixAddr::new_abstract(name).unwrap();
+    sendto(tx.as_raw_fd(), b"hello", &dest, MsgFlags::empty()).unwrap();
+
+    let mut buf = [0u8; 5];
+    recv(rx.as_raw_fd(), &mut buf, MsgFlags::empty()).unwrap();
+    assert_eq!(&buf, b"hello");
+}
+
 // Test creating and using named system control sockets
 #[cfg(apple_targets)]
 #[test]
@@ -3160,3 +3595,114 @@ fn can_open_routing_socket() {
         socket(AddressFamily::Route, SockType::Raw, SockFlag::empty(), None)
             .expect("Failed to open routing socket");
 }
+
+// Writing to a socket whose peer has gone away must fail with EPIPE rather
+// than kill the process.  Rust ignores SIGPIPE by default, so do the write in
+// a child that restores the default disposition.  On Apple targets, this
+// covers the emulation of MSG_NOSIGNAL.
+#[cfg(any(
+    linux_android,
+    apple_targets,
+    freebsdlike,
+    solarish,
+    netbsdlike,
+    target_os = "fuchsia",
+    target_os = "haiku"
+))]
+#[test]
+fn test_send_msg_nosignal_epipe() {
+    use nix::errno::Errno;
+    use nix::sys::signal::{signal, SigHandler, Signal};
+    use nix::sys::socket::{
+        send, sendmsg, socketpair, MsgFlags, SockFlag, SockType,
+    };
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+    use std::io::IoSlice;
+
+    let _m = crate::FORK_MTX.lock();
+
+    let (fd, peer) = socketpair(
+        AddressFamily::Unix,
+        SockType::Stream,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    drop(peer);
+
+    // Safe: The child only calls `signal`, `send`, `sendmsg`, `getsockopt`
+    // and `_exit`.  Without control messages, `sendmsg` doesn't allocate.
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            unsafe { signal(Signal::SIGPIPE, SigHandler::SigDfl) }.unwrap();
+            let sent = send(fd.as_raw_fd(), b"x", MsgFlags::MSG_NOSIGNAL);
+            let iov = [IoSlice::new(b"x")];
+            let sent_msg = sendmsg::<()>(
+                fd.as_raw_fd(),
+                &iov,
+                &[],
+                MsgFlags::MSG_NOSIGNAL,
+                None,
+            );
+            // The emulation must not leave the option behind
+            #[cfg(apple_targets)]
+            let emulated = {
+                use nix::sys::socket::{getsockopt, sockopt};
+                getsockopt(&fd, sockopt::NoSigPipe) == Ok(false)
+            };
+            #[cfg(not(apple_targets))]
+            let emulated = true;
+            let code = match (sent, sent_msg, emulated) {
+                (Err(Errno::EPIPE), Err(Errno::EPIPE), true) => 0,
+                _ => 1,
+            };
+            unsafe { libc::_exit(code) }
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[cfg(any(apple_targets, freebsdlike, target_os = "netbsd"))]
+#[test]
+fn test_send_nosigpipe_epipe() {
+    use nix::errno::Errno;
+    use nix::sys::signal::{signal, SigHandler, Signal};
+    use nix::sys::socket::{
+        send, setsockopt, socketpair, sockopt, MsgFlags, SockFlag, SockType,
+    };
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+
+    let _m = crate::FORK_MTX.lock();
+
+    let (fd, peer) = socketpair(
+        AddressFamily::Unix,
+        SockType::Stream,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    setsockopt(&fd, sockopt::NoSigPipe, &true).unwrap();
+    drop(peer);
+
+    // Safe: The child only calls `signal`, `send` and `_exit`, which are
+    // async-signal-safe.
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            let code = unsafe {
+                signal(Signal::SIGPIPE, SigHandler::SigDfl).unwrap();
+                match send(fd.as_raw_fd(), b"x", MsgFlags::empty()) {
+                    Err(Errno::EPIPE) => 0,
+                    _ => 1,
+                }
+            };
+            unsafe { libc::_exit(code) }
+        }
+        ForkResult::Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
@@ -248,6 +248,70 @@ fn test_so_type_unknown() {
     assert_eq!(Err(Errno::EINVAL), getsockopt(&sockfd, sockopt::SockType));
 }
 
+#[cfg(target_os = "linux")]
+const ACCEPT_ALL: [libc::sock_filter; 1] = [libc::sock_filter {
+    code: (libc::BPF_RET | libc::BPF_K) as u16,
+    jt: 0,
+    jf: 0,
+    k: u32::MAX,
+}];
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_attach_filter() {
+    use nix::errno::Errno;
+
+    require_capability!("test_attach_filter", CAP_NET_RAW);
+    let fd = socket(
+        AddressFamily::Packet,
+        SockType::Raw,
+        SockFlag::empty(),
+        SockProtocol::EthAll,
+    )
+    .unwrap();
+
+    setsockopt(&fd, sockopt::AttachFilter::default(), &ACCEPT_ALL).unwrap();
+    setsockopt(&fd, sockopt::DetachFilter, &()).unwrap();
+    assert_eq!(
+        setsockopt(&fd, sockopt::DetachFilter, &()).unwrap_err(),
+        Errno::ENOENT
+    );
+    let empty: &[libc::sock_filter] = &[];
+    assert_eq!(
+        setsockopt(&fd, sockopt::AttachFilter::default(), &empty).unwrap_err(),
+        Errno::EINVAL
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_lock_filter() {
+    use nix::errno::Errno;
+
+    let fd = socket(
+        AddressFamily::Inet,
+        SockType::Datagram,
+        SockFlag::empty(),
+        None,
+    )
+    .unwrap();
+
+    setsockopt(&fd, sockopt::AttachFilter::default(), &ACCEPT_ALL.to_vec())
+        .unwrap();
+    assert!(!getsockopt(&fd, sockopt::LockFilter).unwrap());
+    setsockopt(&fd, sockopt::LockFilter, &true).unwrap();
+    assert!(getsockopt(&fd, sockopt::LockFilter).unwrap());
+    assert_eq!(
+        setsockopt(&fd, sockopt::DetachFilter, &()).unwrap_err(),
+        Errno::EPERM
+    );
+    assert_eq!(
+        setsockopt(&fd, sockopt::AttachFilter::default(), &ACCEPT_ALL)
+            .unwrap_err(),
+        Errno::EPERM
+    );
+}
+
 // The CI doesn't supported getsockopt and setsockopt on emulated processors.
 // It's believed to be a QEMU issue; the tests run ok on a fully emulated
 // system.  Current CI just runs the binary with QEMU but the kernel remains the
@@ -318,6 +382,59 @@ fn test_tcp_function_blk_alias() {
     setsockopt(&fd, sockopt::TcpFunctionBlk, &tfs).unwrap();
 }
 
+#[test]
+#[cfg(linux_android)]
+fn test_ip_freebind() {
+    use nix::errno::Errno;
+    use nix::sys::socket::{bind, SockaddrIn};
+
+    // From TEST-NET-1, which is never assigned to an interface
+    let addr = SockaddrIn::new(192, 0, 2, 1, 0);
+
+    let fd = socket(
+        AddressFamily::Inet,
+        SockType::Datagram,
+        SockFlag::empty(),
+        None,
+    )
+    .unwrap();
+    assert!(!getsockopt(&fd, sockopt::IpFreebind).unwrap());
+    assert_eq!(bind(fd.as_raw_fd(), &addr), Err(Errno::EADDRNOTAVAIL));
+
+    setsockopt(&fd, sockopt::IpFreebind, &true).unwrap();
+    assert!(getsockopt(&fd, sockopt::IpFreebind).unwrap());
+    bind(fd.as_raw_fd(), &addr).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_ip_transparent() {
+    use caps::{CapSet, Capability};
+    use nix::errno::Errno;
+
+    let fd = socket(
+        AddressFamily::Inet,
+        SockType::Stream,
+        SockFlag::empty(),
+        None,
+    )
+    .unwrap();
+    assert!(!getsockopt(&fd, sockopt::IpTransparent).unwrap());
+
+    // Capabilities are per thread, so drop them in a thread of our own
+    std::thread::spawn(move || {
+        for cap in [Capability::CAP_NET_ADMIN, Capability::CAP_NET_RAW] {
+            caps::drop(None, CapSet::Effective, cap).unwrap();
+        }
+        assert_eq!(
+            setsockopt(&fd, sockopt::IpTransparent, &true),
+            Err(Errno::EPERM)
+        );
+    })
+    .join()
+    .unwrap();
+}
+
 #[test]
 #[cfg(linux_android)]
 fn test_bindtodevice() {
@@ -365,6 +482,42 @@ fn test_so_tcp_keepalive() {
     }
 }
 
+#[test]
+#[cfg(any(apple_targets, freebsdlike, linux_android))]
+fn test_set_keepalive() {
+    use nix::sys::socket::set_keepalive;
+    use std::time::Duration;
+
+    let fd = socket(
+        AddressFamily::Inet,
+        SockType::Stream,
+        SockFlag::empty(),
+        SockProtocol::Tcp,
+    )
+    .unwrap();
+    set_keepalive(
+        &fd,
+        Duration::from_millis(42_500),
+        Duration::from_secs(7),
+        3,
+    )
+    .unwrap();
+
+    assert!(getsockopt(&fd, sockopt::KeepAlive).unwrap());
+    #[cfg(apple_targets)]
+    assert_eq!(getsockopt(&fd, sockopt::TcpKeepAlive).unwrap(), 42);
+    #[cfg(not(apple_targets))]
+    assert_eq!(getsockopt(&fd, sockopt::TcpKeepIdle).unwrap(), 42);
+    assert_eq!(getsockopt(&fd, sockopt::TcpKeepInterval).unwrap(), 7);
+    assert_eq!(getsockopt(&fd, sockopt::TcpKeepCount).unwrap(), 3);
+
+    let too_long = Duration::from_secs(u64::from(u32::MAX) + 1);
+    assert_eq!(
+        set_keepalive(&fd, too_long, Duration::from_secs(7), 3),
+        Err(nix::errno::Errno::EINVAL)
+    );
+}
+
 #[test]
 #[cfg(linux_android)]
 #[cfg_attr(qemu, ignore)]
@@ -732,6 +885,55 @@ fn test_tcp_fast_open_connect() {
     ));
 }
 
+#[test]
+#[cfg(linux_android)]
+fn test_tcp_fast_open() {
+    use nix::sys::socket::{
+        accept, bind, getsockname, listen, sendto, Backlog, MsgFlags,
+        SockaddrIn,
+    };
+    use std::net::SocketAddrV4;
+    use std::os::unix::io::AsRawFd;
+
+    let listener = socket(
+        AddressFamily::Inet,
+        SockType::Stream,
+        SockFlag::empty(),
+        SockProtocol::Tcp,
+    )
+    .unwrap();
+    setsockopt(&listener, sockopt::TcpFastOpen, &5)
+        .expect("setting TCP_FASTOPEN on an inet stream socket should succeed");
+    assert_eq!(getsockopt(&listener, sockopt::TcpFastOpen).unwrap(), 5);
+    let localhost =
+        SockaddrIn::from(SocketAddrV4::new(std::net::Ipv4Addr::LOCALHOST, 0));
+    bind(listener.as_raw_fd(), &localhost).unwrap();
+    let addr: SockaddrIn = getsockname(listener.as_raw_fd()).unwrap();
+    listen(&listener, Backlog::new(1).unwrap()).unwrap();
+
+    let client = socket(
+        AddressFamily::Inet,
+        SockType::Stream,
+        SockFlag::empty(),
+        SockProtocol::Tcp,
+    )
+    .unwrap();
+    // Without a cached cookie the kernel falls back to a normal handshake,
+    // so the data must arrive either way.
+    match sendto(client.as_raw_fd(), b"hello", &addr, MsgFlags::MSG_FASTOPEN) {
+        Err(nix::errno::Errno::EOPNOTSUPP) => {
+            skip!("TCP Fast Open is disabled by net.ipv4.tcp_fastopen");
+        }
+        r => assert_eq!(r.unwrap(), 5),
+    }
+
+    let conn = accept(listener.as_raw_fd()).unwrap();
+    let conn = unsafe { OwnedFd::from_raw_fd(conn) };
+    let mut buf = [0u8; 5];
+    assert_eq!(nix::unistd::read(&conn, &mut buf).unwrap(), 5);
+    assert_eq!(&buf, b"hello");
+}
+
 #[cfg(linux_android)]
 #[test]
 fn can_get_peercred_on_unix_socket() {
@@ -750,6 +952,32 @@ fn can_get_peercred_on_unix_socket() {
     assert_ne!(a_cred.pid(), 0);
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn can_get_peersec_on_unix_socket() {
+    use nix::errno::Errno;
+    use nix::sys::socket::{socketpair, sockopt, SockFlag, SockType};
+    use std::os::unix::ffi::OsStrExt;
+
+    let (a, b) = socketpair(
+        AddressFamily::Unix,
+        SockType::Stream,
+        None,
+        SockFlag::empty(),
+    )
+    .unwrap();
+    let a_sec = match getsockopt(&a, sockopt::PeerSec) {
+        Err(Errno::ENOPROTOOPT) => {
+            skip!("No security module provides SO_PEERSEC. Skipping test.")
+        }
+        r => r.unwrap(),
+    };
+    let b_sec = getsockopt(&b, sockopt::PeerSec).unwrap();
+    // Both ends were created by this process, so they carry the same label
+    assert_eq!(a_sec, b_sec);
+    assert!(!a_sec.as_bytes().contains(&0));
+}
+
 #[test]
 fn is_socket_type_unix() {
     use nix::sys::socket::{socketpair, sockopt, SockFlag, SockType};
@@ -1168,3 +1396,35 @@ fn test_exclbind() {
         Err(Errno::EADDRINUSE)
     );
 }
+
+#[cfg(not(solarish))]
+#[test]
+fn test_reuseport_group() {
+    use nix::errno::Errno;
+    use nix::sys::socket::{bind, getsockname, SockaddrIn};
+
+    let new_socket = || {
+        socket(
+            AddressFamily::Inet,
+            SockType::Datagram,
+            SockFlag::empty(),
+            None,
+        )
+        .unwrap()
+    };
+    let fd1 = new_socket();
+    assert!(!getsockopt(&fd1, sockopt::ReusePort).unwrap());
+    setsockopt(&fd1, sockopt::ReusePort, &true).unwrap();
+    bind(fd1.as_raw_fd(), &SockaddrIn::new(127, 0, 0, 1, 0)).unwrap();
+    let addr: SockaddrIn = getsockname(fd1.as_raw_fd()).unwrap();
+
+    // Without the option, the port is taken
+    let fd2 = new_socket();
+    assert_eq!(bind(fd2.as_raw_fd(), &addr), Err(Errno::EADDRINUSE));
+
+    let fd3 = new_socket();
+    setsockopt(&fd3, sockopt::ReusePort, &true).unwrap();
+    bind(fd3.as_raw_fd(), &addr).unwrap();
+    assert!(getsockopt(&fd1, sockopt::ReusePort).unwrap());
+    assert!(getsockopt(&fd3, sockopt::ReusePort).unwrap());
+}
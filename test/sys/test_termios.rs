@@ -115,3 +115,78 @@ fn test_local_flags() {
     let read = read(&pty.master, &mut buf).unwrap_err();
     assert_eq!(read, Errno::EAGAIN);
 }
+
+// Test the line control functions on a pty
+#[test]
+fn test_line_control() {
+    use nix::sys::termios::{FlowArg, FlushArg};
+
+    // openpty uses ptname(3) internally
+    let _m = crate::PTSNAME_MTX.lock();
+
+    let pty = openpty(None, None).unwrap();
+    let flags = fcntl::fcntl(&pty.slave, fcntl::F_GETFL).unwrap();
+    let new_flags =
+        fcntl::OFlag::from_bits_truncate(flags) | fcntl::OFlag::O_NONBLOCK;
+    fcntl::fcntl(pty.slave.as_fd(), fcntl::F_SETFL(new_flags)).unwrap();
+
+    // Queue a line of input on the slave, then discard it.
+    write_all(&pty.master, b"foofoofoo\n");
+    termios::tcdrain(&pty.master).unwrap();
+    termios::tcflush(&pty.slave, FlushArg::TCIFLUSH).unwrap();
+    let mut buf = [0u8; 10];
+    assert_eq!(read(&pty.slave, &mut buf), Err(Errno::EAGAIN));
+
+    termios::tcflush(&pty.slave, FlushArg::TCIOFLUSH).unwrap();
+    termios::tcdrain(&pty.slave).unwrap();
+    termios::tcflow(&pty.slave, FlowArg::TCOOFF).unwrap();
+    termios::tcflow(&pty.slave, FlowArg::TCOON).unwrap();
+    termios::tcsendbreak(&pty.slave, 0).unwrap();
+}
+
+#[test]
+fn test_termios_builder_8n1() {
+    use nix::sys::termios::{
+        cfgetospeed, ControlFlags, InputFlags, Parity, TermiosBuilder,
+    };
+
+    let _m = crate::PTSNAME_MTX.lock();
+    let pty = openpty(None, None).expect("openpty failed");
+    let termios = tcgetattr(&pty.slave).unwrap();
+
+    let termios = TermiosBuilder::new(termios)
+        .baud(BaudRate::B115200)
+        .data_bits(8)
+        .parity(Parity::None)
+        .stop_bits(1)
+        .raw()
+        .build()
+        .unwrap();
+
+    let cflags = termios.control_flags;
+    assert_eq!(cflags & ControlFlags::CSIZE, ControlFlags::CS8);
+    assert!(!cflags.intersects(ControlFlags::PARENB | ControlFlags::CSTOPB));
+    assert!(cflags.contains(ControlFlags::CREAD | ControlFlags::CLOCAL));
+    assert!(!termios.input_flags.contains(InputFlags::INPCK));
+    assert!(!termios.local_flags.contains(LocalFlags::ICANON));
+    // The conversion is needed on BSDs, where speeds are u32
+    #[allow(clippy::useless_conversion)]
+    let speed = BaudRate::B115200.into();
+    assert_eq!(cfgetospeed(&termios), speed);
+
+    let termios = TermiosBuilder::new(termios)
+        .data_bits(7)
+        .parity(Parity::Odd)
+        .stop_bits(2)
+        .build()
+        .unwrap();
+    let cflags = termios.control_flags;
+    assert_eq!(cflags & ControlFlags::CSIZE, ControlFlags::CS7);
+    assert!(cflags.contains(
+        ControlFlags::PARENB | ControlFlags::PARODD | ControlFlags::CSTOPB
+    ));
+    assert!(termios.input_flags.contains(InputFlags::INPCK));
+
+    let err = TermiosBuilder::new(termios).data_bits(9).build();
+    assert_eq!(err, Err(Errno::EINVAL));
+}
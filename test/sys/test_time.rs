@@ -14,6 +14,20 @@ pub fn test_timespec() {
     );
 }
 
+#[test]
+pub fn test_timespec_normalization() {
+    assert_eq!(TimeSpec::ZERO, TimeSpec::zero());
+
+    let ts = TimeSpec::milliseconds(1500);
+    assert_eq!((ts.tv_sec(), ts.tv_nsec()), (1, 500_000_000));
+    let ts = TimeSpec::microseconds(2_000_001);
+    assert_eq!((ts.tv_sec(), ts.tv_nsec()), (2, 1_000));
+    let ts = TimeSpec::nanoseconds(-1);
+    assert_eq!((ts.tv_sec(), ts.tv_nsec()), (-1, 999_999_999));
+    let ts = TimeSpec::milliseconds(-1500);
+    assert_eq!((ts.tv_sec(), ts.tv_nsec()), (-2, 500_000_000));
+}
+
 #[test]
 pub fn test_timespec_from() {
     let duration = Duration::new(123, 123_456_789);
@@ -23,6 +37,49 @@ pub fn test_timespec_from() {
     assert_eq!(Duration::from(timespec), duration);
 }
 
+#[test]
+pub fn test_timespec_saturating() {
+    let a = TimeSpec::new(1, 600_000_000);
+    let b = TimeSpec::new(2, 700_000_000);
+
+    let sum = a.saturating_add(b);
+    assert_eq!((sum.tv_sec(), sum.tv_nsec()), (4, 300_000_000));
+    let diff = b.saturating_sub(a);
+    assert_eq!((diff.tv_sec(), diff.tv_nsec()), (1, 100_000_000));
+    assert_eq!(a.saturating_sub(b), TimeSpec::ZERO);
+
+    // Out of range values are clamped to the largest TimeSpec
+    let max =
+        TimeSpec::new(libc::time_t::MAX, 0).saturating_add(TimeSpec::ZERO);
+    assert!(max.tv_nsec() < 1_000_000_000);
+    assert_eq!(max.saturating_add(max), max);
+    assert_eq!(max.saturating_add(TimeSpec::nanoseconds(1)), max);
+}
+
+#[test]
+pub fn test_timespec_duration() {
+    let duration = Duration::from_millis(500);
+    let ts = TimeSpec::from_duration(duration);
+    assert_eq!(ts.to_duration(), duration);
+    assert_eq!(TimeSpec::nanoseconds(-1).to_duration(), Duration::ZERO);
+
+    let later = ts.saturating_add(TimeSpec::from_duration(duration));
+    assert_eq!(later.elapsed_since(&ts), duration);
+    assert_eq!(ts.elapsed_since(&later), Duration::ZERO);
+}
+
+#[test]
+pub fn test_timeval_saturating() {
+    let a = TimeVal::new(1, 600_000);
+    let b = TimeVal::new(2, 700_000);
+
+    let sum = a.saturating_add(b);
+    assert_eq!((sum.tv_sec(), sum.tv_usec()), (4, 300_000));
+    let diff = b.saturating_sub(a);
+    assert_eq!((diff.tv_sec(), diff.tv_usec()), (1, 100_000));
+    assert_eq!(a.saturating_sub(b), TimeVal::ZERO);
+}
+
 #[test]
 pub fn test_timespec_neg() {
     let a = TimeSpec::seconds(1) + TimeSpec::nanoseconds(123);
@@ -63,6 +120,18 @@ pub fn test_timeval() {
     );
 }
 
+#[test]
+pub fn test_timeval_normalization() {
+    assert_eq!(TimeVal::ZERO, TimeVal::zero());
+
+    let tv = TimeVal::milliseconds(1500);
+    assert_eq!((tv.tv_sec(), tv.tv_usec()), (1, 500_000));
+    let tv = TimeVal::nanoseconds(2_000_001_999);
+    assert_eq!((tv.tv_sec(), tv.tv_usec()), (2, 1));
+    let tv = TimeVal::microseconds(-1);
+    assert_eq!((tv.tv_sec(), tv.tv_usec()), (-1, 999_999));
+}
+
 #[test]
 pub fn test_timeval_ord() {
     assert_eq!(TimeVal::seconds(1), TimeVal::microseconds(1_000_000));
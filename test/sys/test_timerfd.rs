@@ -67,3 +67,79 @@ pub fn test_timerfd_unset() {
 
     assert!(timer.get().unwrap().is_none());
 }
+
+#[test]
+pub fn test_timerfd_boottime_alarm() {
+    require_capability!("test_timerfd_boottime_alarm", CAP_WAKE_ALARM);
+
+    let timer = match TimerFd::new(
+        ClockId::CLOCK_BOOTTIME_ALARM,
+        TimerFlags::empty(),
+    ) {
+        // Alarm clocks need a wakeup-capable RTC
+        Err(nix::errno::Errno::EOPNOTSUPP) => {
+            skip!("No RTC for CLOCK_BOOTTIME_ALARM. Skipping test.")
+        }
+        res => res.unwrap(),
+    };
+
+    let before = Instant::now();
+    timer
+        .set(
+            Expiration::OneShot(TimeSpec::milliseconds(100)),
+            TimerSetTimeFlags::empty(),
+        )
+        .unwrap();
+    timer.wait().unwrap();
+    assert!(before.elapsed().as_millis() >= 90);
+}
+
+#[test]
+pub fn test_timerfd_alarm_eperm() {
+    use caps::{has_cap, CapSet, Capability};
+
+    if has_cap(None, CapSet::Effective, Capability::CAP_WAKE_ALARM).unwrap() {
+        skip!("test_timerfd_alarm_eperm requires lacking CAP_WAKE_ALARM. Skipping test.");
+    }
+
+    let res = TimerFd::new(ClockId::CLOCK_BOOTTIME_ALARM, TimerFlags::empty());
+    assert_eq!(res.unwrap_err(), nix::errno::Errno::EPERM);
+}
+
+#[test]
+pub fn test_timerfd_nonblocking() {
+    use nix::errno::Errno;
+
+    let timer = TimerFd::new(
+        ClockId::CLOCK_MONOTONIC,
+        TimerFlags::TFD_NONBLOCK | TimerFlags::TFD_CLOEXEC,
+    )
+    .unwrap();
+
+    // Neither an unset nor an unexpired timer should block.
+    assert_eq!(timer.wait(), Err(Errno::EAGAIN));
+    timer
+        .set(
+            Expiration::OneShot(TimeSpec::seconds(60)),
+            TimerSetTimeFlags::empty(),
+        )
+        .unwrap();
+    assert_eq!(timer.wait(), Err(Errno::EAGAIN));
+}
+
+#[test]
+pub fn test_timerfd_cloexec_flag() {
+    use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+
+    let timer = TimerFd::new(ClockId::CLOCK_MONOTONIC, TimerFlags::TFD_CLOEXEC)
+        .unwrap();
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&timer, FcntlArg::F_GETFD).unwrap());
+    assert!(flags.contains(FdFlag::FD_CLOEXEC));
+
+    let timer =
+        TimerFd::new(ClockId::CLOCK_MONOTONIC, TimerFlags::empty()).unwrap();
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&timer, FcntlArg::F_GETFD).unwrap());
+    assert!(!flags.contains(FdFlag::FD_CLOEXEC));
+}
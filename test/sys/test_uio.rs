@@ -268,7 +268,7 @@ fn test_process_vm_readv() {
             }
             let _ = write(w, b"\0");
             loop {
-                pause();
+                let _ = pause();
             }
         }
     }
@@ -0,0 +1,16 @@
+use nix::errno::Errno;
+use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+use nix::sys::userfaultfd::*;
+
+#[test]
+fn test_userfaultfd_cloexec() {
+    let uffd = match userfaultfd(UffdFlags::O_CLOEXEC) {
+        Err(Errno::EPERM | Errno::ENOSYS) => {
+            skip!("userfaultfd is not available. Skipping test.")
+        }
+        res => res.unwrap(),
+    };
+    let flags =
+        FdFlag::from_bits_retain(fcntl(&uffd, FcntlArg::F_GETFD).unwrap());
+    assert!(flags.contains(FdFlag::FD_CLOEXEC));
+}
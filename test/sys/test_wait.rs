@@ -13,7 +13,7 @@ fn test_wait_signal() {
     // Safe: The child only calls `pause` and/or `_exit`, which are async-signal-safe.
     match unsafe { fork() }.expect("Error: Fork Failed") {
         Child => {
-            pause();
+            let _ = pause();
             unsafe { _exit(123) }
         }
         Parent { child } => {
@@ -45,7 +45,7 @@ fn test_waitid_signal() {
     // Safe: The child only calls `pause` and/or `_exit`, which are async-signal-safe.
     match unsafe { fork() }.expect("Error: Fork Failed") {
         Child => {
-            pause();
+            let _ = pause();
             unsafe { _exit(123) }
         }
         Parent { child } => {
@@ -104,6 +104,87 @@ fn test_waitid_exit() {
     }
 }
 
+#[test]
+#[cfg(not(any(target_os = "redox", target_os = "haiku")))]
+fn test_wait_stop_continue() {
+    let _m = crate::FORK_MTX.lock();
+
+    // Safe: The child only calls `pause`, which is async-signal-safe.
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => loop {
+            let _ = pause();
+        },
+        Parent { child } => {
+            assert_eq!(
+                waitpid(child, Some(WaitPidFlag::WNOHANG)),
+                Ok(WaitStatus::StillAlive)
+            );
+
+            kill(child, SIGSTOP).unwrap();
+            assert_eq!(
+                waitpid(child, Some(WaitPidFlag::WUNTRACED)),
+                Ok(WaitStatus::Stopped(child, SIGSTOP))
+            );
+
+            kill(child, SIGCONT).unwrap();
+            assert_eq!(
+                waitpid(child, Some(WaitPidFlag::WCONTINUED)),
+                Ok(WaitStatus::Continued(child))
+            );
+
+            kill(child, SIGKILL).unwrap();
+            assert_eq!(
+                waitpid(child, None),
+                Ok(WaitStatus::Signaled(child, SIGKILL, false))
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    all(target_os = "linux", not(target_env = "uclibc")),
+))]
+fn test_waitid_stop_continue() {
+    let _m = crate::FORK_MTX.lock();
+
+    // Safe: The child only calls `pause`, which is async-signal-safe.
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => loop {
+            let _ = pause();
+        },
+        Parent { child } => {
+            assert_eq!(
+                waitid(
+                    Id::Pid(child),
+                    WaitPidFlag::WEXITED | WaitPidFlag::WNOHANG
+                ),
+                Ok(WaitStatus::StillAlive)
+            );
+
+            kill(child, SIGSTOP).unwrap();
+            assert_eq!(
+                waitid(Id::Pid(child), WaitPidFlag::WSTOPPED),
+                Ok(WaitStatus::Stopped(child, SIGSTOP))
+            );
+
+            kill(child, SIGCONT).unwrap();
+            assert_eq!(
+                waitid(Id::Pid(child), WaitPidFlag::WCONTINUED),
+                Ok(WaitStatus::Continued(child))
+            );
+
+            kill(child, SIGKILL).unwrap();
+            assert_eq!(
+                waitid(Id::Pid(child), WaitPidFlag::WEXITED),
+                Ok(WaitStatus::Signaled(child, SIGKILL, false))
+            );
+        }
+    }
+}
+
 #[test]
 fn test_waitstatus_from_raw() {
     let pid = Pid::from_raw(1);
@@ -0,0 +1,42 @@
+use nix::errno::Errno;
+use nix::sys::xattr::*;
+use tempfile::NamedTempFile;
+
+#[test]
+fn test_setxattr_getxattr() {
+    let tmp = NamedTempFile::new().unwrap();
+    let name = "user.nix-test";
+
+    match setxattr(tmp.path(), name, b"value", XattrFlags::empty()) {
+        Ok(()) => (),
+        Err(Errno::ENOTSUP) => {
+            skip!("File system does not support user xattrs. Skipping test.")
+        }
+        Err(e) => panic!("setxattr failed: {e}"),
+    }
+    assert_eq!(getxattr(tmp.path(), name).unwrap(), b"value");
+    assert_eq!(fgetxattr(&tmp, name).unwrap(), b"value");
+    assert!(listxattr(tmp.path())
+        .unwrap()
+        .iter()
+        .any(|n| n.as_os_str() == name));
+
+    // XATTR_CREATE refuses to overwrite an existing attribute
+    assert_eq!(
+        setxattr(tmp.path(), name, b"other", XattrFlags::XATTR_CREATE),
+        Err(Errno::EEXIST)
+    );
+    fsetxattr(&tmp, name, b"other", XattrFlags::XATTR_REPLACE).unwrap();
+    assert_eq!(getxattr(tmp.path(), name).unwrap(), b"other");
+
+    removexattr(tmp.path(), name).unwrap();
+    assert!(!flistxattr(&tmp)
+        .unwrap()
+        .iter()
+        .any(|n| n.as_os_str() == name));
+    // XATTR_REPLACE refuses to create a missing attribute
+    assert!(
+        setxattr(tmp.path(), name, b"value", XattrFlags::XATTR_REPLACE)
+            .is_err()
+    );
+}
@@ -17,6 +17,8 @@ mod test_kmod;
 mod test_mq;
 #[cfg(not(target_os = "redox"))]
 mod test_net;
+#[cfg(any(linux_android, bsd))]
+mod test_netdb;
 mod test_nix_path;
 mod test_poll;
 #[cfg(not(any(
@@ -78,6 +80,9 @@ pub static GROUPS_MTX: Mutex<()> = Mutex::new(());
 pub static KMOD_MTX: Mutex<()> = Mutex::new(());
 /// Any test that calls ptsname(3) must grab this mutex.
 pub static PTSNAME_MTX: Mutex<()> = Mutex::new(());
+/// Any test that calls getservbyname(3) or getservbyport(3) must grab this
+/// mutex.
+pub static SERVENT_MTX: Mutex<()> = Mutex::new(());
 /// Any test that alters signal handling must grab this mutex.
 pub static SIGNAL_MTX: Mutex<()> = Mutex::new(());
 
@@ -56,3 +56,53 @@ fn rewind() {
     assert_eq!(entries1, entries2);
     assert_eq!(entries2, entries3);
 }
+
+#[test]
+fn open_regular_file() {
+    use nix::errno::Errno;
+    use nix::fcntl::{open, AT_FDCWD};
+
+    let tmp = tempdir().unwrap();
+    let path = tmp.path().join("foo");
+    File::create(&path).unwrap();
+
+    let flags = OFlag::O_RDONLY | OFlag::O_CLOEXEC;
+    assert_eq!(
+        Dir::open(&path, flags, Mode::empty()).unwrap_err(),
+        Errno::ENOTDIR
+    );
+    assert_eq!(
+        Dir::openat(AT_FDCWD, &path, flags, Mode::empty()).unwrap_err(),
+        Errno::ENOTDIR
+    );
+
+    let fd = open(&path, flags, Mode::empty()).unwrap();
+    assert_eq!(Dir::from_fd(fd).unwrap_err(), Errno::ENOTDIR);
+}
+
+#[test]
+fn openat_dir_descends_relatively() {
+    use nix::fcntl::openat_dir;
+
+    let tmp = tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("a/b")).unwrap();
+    File::create(tmp.path().join("a/b/file")).unwrap();
+    std::os::unix::fs::symlink("a", tmp.path().join("link")).unwrap();
+
+    let root = Dir::open(tmp.path(), flags(), Mode::empty()).unwrap();
+    let a = openat_dir(&root, "a").unwrap();
+    let mut b = openat_dir(&a, "b").unwrap();
+    let names: Vec<_> = b
+        .iter()
+        .map(|e| e.unwrap().file_name().to_str().unwrap().to_owned())
+        .filter(|name| name != "." && name != "..")
+        .collect();
+    assert_eq!(names, ["file"]);
+
+    // Symlinks and regular files aren't followed or opened
+    assert!(openat_dir(&root, "link").is_err());
+    assert_eq!(
+        openat_dir(&b, "file").unwrap_err(),
+        nix::errno::Errno::ENOTDIR
+    );
+}
@@ -310,6 +310,25 @@ mod linux_android {
 
     use crate::*;
 
+    #[test]
+    fn test_pipe_size() {
+        let (rd, wr) = pipe().unwrap();
+        let initial = get_pipe_size(&wr).unwrap();
+        assert!(initial > 0);
+        assert_eq!(get_pipe_size(&rd).unwrap(), initial);
+
+        // The kernel rounds the size up to a power-of-two number of pages
+        let wanted = initial * 2 + 1;
+        let actual = set_pipe_size(&wr, wanted).unwrap();
+        assert!(actual >= wanted);
+        assert_eq!(get_pipe_size(&rd).unwrap(), actual);
+
+        // Can't shrink the pipe below the data it holds
+        let data = vec![0u8; initial * 2];
+        write(&wr, &data).unwrap();
+        assert_eq!(set_pipe_size(&wr, 1), Err(nix::errno::Errno::EBUSY));
+    }
+
     #[test]
     fn test_splice() {
         const CONTENTS: &[u8] = b"abcdef123456";
@@ -330,6 +349,24 @@ mod linux_android {
         assert_eq!(7, offset);
     }
 
+    #[test]
+    fn test_splice_all() {
+        const CONTENTS: &[u8] = b"abcdef123456";
+        let (rd1, wr1) = pipe().unwrap();
+        let (rd2, wr2) = pipe().unwrap();
+
+        write(&wr1, CONTENTS).unwrap();
+        drop(wr1);
+
+        // Asking for more than is available stops cleanly at EOF
+        let res = splice_all(&rd1, &wr2, 1024).unwrap();
+        assert_eq!(CONTENTS.len(), res);
+
+        let mut buf = [0u8; 1024];
+        assert_eq!(CONTENTS.len(), read(&rd2, &mut buf).unwrap());
+        assert_eq!(CONTENTS, &buf[0..CONTENTS.len()]);
+    }
+
     #[test]
     fn test_tee() {
         let (rd1, wr1) = pipe().unwrap();
@@ -370,6 +407,19 @@ mod linux_android {
         assert_eq!(b"abcdef", &buf[0..6]);
     }
 
+    #[test]
+    fn test_vmsplice_gift() {
+        static DATA: &[u8] = b"gifted bytes";
+
+        let (rd, wr) = pipe().unwrap();
+        let res = vmsplice_gift(&wr, DATA).unwrap();
+        assert_eq!(DATA.len(), res);
+
+        let mut buf = [0u8; 32];
+        assert_eq!(DATA.len(), read(&rd, &mut buf).unwrap());
+        assert_eq!(DATA, &buf[..DATA.len()]);
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn test_fallocate() {
@@ -463,6 +513,42 @@ mod linux_android {
         assert_eq!(None, lock_info(inode));
     }
 
+    #[test]
+    #[cfg(all(target_os = "linux", not(target_env = "musl")))]
+    #[cfg_attr(target_env = "uclibc", ignore)] // uclibc doesn't support OFD locks, but the test should still compile
+    fn test_ofd_lock_contention() {
+        use nix::errno::Errno;
+        use std::mem;
+
+        // Two separate opens of the same file, in the same process
+        let tmp1 = NamedTempFile::new().unwrap();
+        let tmp2 = tmp1.reopen().unwrap();
+
+        let mut flock: libc::flock = unsafe {
+            mem::zeroed() // required for Linux/mips
+        };
+        flock.l_type = libc::F_WRLCK as libc::c_short;
+        flock.l_whence = libc::SEEK_SET as libc::c_short;
+        fcntl(&tmp1, FcntlArg::F_OFD_SETLK(&flock)).expect("write lock failed");
+
+        assert_eq!(
+            fcntl(&tmp2, FcntlArg::F_OFD_SETLK(&flock)),
+            Err(Errno::EAGAIN)
+        );
+        let mut query = flock;
+        fcntl(&tmp2, FcntlArg::F_OFD_GETLK(&mut query)).unwrap();
+        assert_eq!(query.l_type, libc::F_WRLCK as libc::c_short);
+        // OFD locks are not owned by any process
+        assert_eq!(query.l_pid, -1);
+
+        flock.l_type = libc::F_UNLCK as libc::c_short;
+        fcntl(&tmp1, FcntlArg::F_OFD_SETLK(&flock))
+            .expect("write unlock failed");
+        flock.l_type = libc::F_WRLCK as libc::c_short;
+        fcntl(&tmp2, FcntlArg::F_OFD_SETLK(&flock))
+            .expect("second write lock failed");
+    }
+
     #[cfg(all(target_os = "linux", not(target_env = "musl")))]
     fn lock_info(inode: usize) -> Option<(String, String)> {
         use std::{fs::File, io::BufReader};
@@ -725,6 +811,29 @@ mod test_flock {
         }
     }
 
+    /// Two independently opened descriptors contend for an exclusive lock.
+    #[test]
+    fn flock_contention() {
+        use nix::errno::Errno;
+
+        let file1 = NamedTempFile::new().unwrap();
+        let file2 = file1.reopen().unwrap();
+
+        flock(&file1, FlockArg::LockExclusive).unwrap();
+        assert_eq!(
+            flock(&file2, FlockArg::LockExclusiveNonblock),
+            Err(Errno::EWOULDBLOCK)
+        );
+        assert_eq!(
+            flock(&file2, FlockArg::LockSharedNonblock),
+            Err(Errno::EWOULDBLOCK)
+        );
+
+        flock(&file1, FlockArg::Unlock).unwrap();
+        flock(&file2, FlockArg::LockExclusiveNonblock).unwrap();
+        flock(&file2, FlockArg::Unlock).unwrap();
+    }
+
     /// A shared lock can be upgraded
     #[test]
     fn upgrade() {
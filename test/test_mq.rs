@@ -109,6 +109,50 @@ fn test_mq_getattr() {
     mq_close(mqd).unwrap();
 }
 
+#[test]
+fn test_mq_attr_builder() {
+    use nix::mqueue::{mq_getattr, mq_unlink};
+    const MSG_SIZE: mq_attr_member_t = 64;
+    let attr = MqAttr::default()
+        .max_messages(5)
+        .message_size(MSG_SIZE)
+        .nonblocking(true);
+    let mq_name = "/attr_test_builder";
+    let oflag = MQ_OFlag::O_CREAT | MQ_OFlag::O_RDONLY;
+    let mode = Mode::S_IWUSR | Mode::S_IRUSR | Mode::S_IRGRP | Mode::S_IROTH;
+    let r = mq_open(mq_name, oflag, mode, Some(&attr));
+    if let Err(Errno::ENOSYS) = r {
+        println!("message queues not supported or module not loaded?");
+        return;
+    };
+    let mqd = r.unwrap();
+
+    let read_attr = mq_getattr(&mqd).unwrap();
+    assert_eq!(read_attr.maxmsg(), 5);
+    assert_eq!(read_attr.msgsize(), MSG_SIZE);
+    assert_eq!(read_attr.curmsgs(), 0);
+    let nonblock = MQ_OFlag::O_NONBLOCK.bits() as mq_attr_member_t;
+    assert_eq!(read_attr.flags() & nonblock, nonblock);
+
+    let mut buf = [0u8; MSG_SIZE as usize];
+    let mut prio = 0u32;
+    assert_eq!(
+        nix::mqueue::mq_receive(&mqd, &mut buf, &mut prio).unwrap_err(),
+        Errno::EAGAIN
+    );
+    mq_close(mqd).unwrap();
+    mq_unlink(mq_name).unwrap();
+
+    // Larger than any system limit, even for privileged processes
+    let too_big = MqAttr::default()
+        .max_messages(1)
+        .message_size(mq_attr_member_t::from(i32::MAX));
+    assert_eq!(
+        mq_open(mq_name, oflag, mode, Some(&too_big)).unwrap_err(),
+        Errno::EINVAL
+    );
+}
+
 // FIXME: Fix failures for mips in QEMU
 #[test]
 #[cfg_attr(
@@ -0,0 +1,307 @@
+use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+use nix::netdb::{
+    connect_any, getaddrinfo, getnameinfo, AddrInfo, AddressInfoError, AiFlags,
+    NiFlags,
+};
+use nix::sys::socket::{
+    getpeername, getsockname, SockProtocol, SockType, SockaddrIn, SockaddrIn6,
+    SockaddrStorage,
+};
+use std::net::TcpListener;
+use std::os::unix::io::AsRawFd;
+
+#[test]
+fn test_getaddrinfo_localhost() {
+    let mut hints = AddrInfo::default();
+    hints.set_socktype(SockType::Stream);
+    let list =
+        getaddrinfo(Some("localhost"), Some("80"), Some(&hints)).unwrap();
+    assert!(!list.is_empty());
+    assert!(list.iter().count() > 0);
+    for ai in &list {
+        assert_eq!(ai.socktype(), Some(SockType::Stream));
+    }
+}
+
+#[test]
+fn test_getaddrinfo_nul_byte() {
+    assert_eq!(
+        getaddrinfo(Some("local\0host"), None, None).unwrap_err(),
+        AddressInfoError::EAI_SYSTEM(nix::errno::Errno::EINVAL)
+    );
+}
+
+#[test]
+fn test_connect_any_localhost() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let mut hints = AddrInfo::default();
+    hints.set_socktype(SockType::Stream);
+    // "localhost" may resolve to ::1 first, which nothing listens on; the
+    // next address must then be tried.
+    let fd = connect_any("localhost", &port.to_string(), Some(&hints)).unwrap();
+    let (_stream, peer) = listener.accept().unwrap();
+
+    let local = getsockname::<SockaddrStorage>(fd.as_raw_fd()).unwrap();
+    assert_eq!(local.as_sockaddr_in().unwrap().port(), peer.port());
+    let remote = getpeername::<SockaddrStorage>(fd.as_raw_fd()).unwrap();
+    assert_eq!(remote.as_sockaddr_in().unwrap().port(), port);
+
+    let fdflags =
+        FdFlag::from_bits_truncate(fcntl(&fd, FcntlArg::F_GETFD).unwrap());
+    assert!(fdflags.contains(FdFlag::FD_CLOEXEC));
+}
+
+#[test]
+fn test_connect_any_refused() {
+    // Find a port that nothing listens on
+    let port = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    };
+
+    let mut hints = AddrInfo::default();
+    hints.set_socktype(SockType::Stream);
+    let err =
+        connect_any("127.0.0.1", &port.to_string(), Some(&hints)).unwrap_err();
+    assert_eq!(
+        err,
+        AddressInfoError::EAI_SYSTEM(nix::errno::Errno::ECONNREFUSED)
+    );
+}
+
+#[test]
+fn test_getnameinfo_numeric() {
+    let flags = NiFlags::NI_NUMERICHOST | NiFlags::NI_NUMERICSERV;
+    let addr = SockaddrStorage::from(std::net::SocketAddr::from((
+        [127, 0, 0, 1],
+        8080,
+    )));
+    assert_eq!(
+        getnameinfo(&addr, flags).unwrap(),
+        ("127.0.0.1".to_string(), "8080".to_string())
+    );
+
+    let addr = SockaddrIn6::from(std::net::SocketAddrV6::new(
+        std::net::Ipv6Addr::LOCALHOST,
+        443,
+        0,
+        0,
+    ));
+    assert_eq!(
+        getnameinfo(&addr, flags).unwrap(),
+        ("::1".to_string(), "443".to_string())
+    );
+}
+
+#[cfg(linux_android)]
+#[test]
+fn test_getnameinfo_bad_family() {
+    let addr = nix::sys::socket::NetlinkAddr::new(0, 0);
+    assert_eq!(
+        getnameinfo(&addr, NiFlags::empty()).unwrap_err(),
+        AddressInfoError::EAI_FAMILY
+    );
+}
+
+#[test]
+fn test_addrinfo_accessors() {
+    let mut hints = AddrInfo::default();
+    hints.set_socktype(SockType::Stream);
+    hints.set_protocol(SockProtocol::Tcp);
+    hints.set_flags(AiFlags::AI_NUMERICHOST | AiFlags::AI_NUMERICSERV);
+
+    let list =
+        getaddrinfo(Some("127.0.0.1"), Some("80"), Some(&hints)).unwrap();
+    let ai = list.iter().next().unwrap();
+    assert_eq!(ai.socktype(), Some(SockType::Stream));
+    assert_eq!(ai.protocol(), Some(SockProtocol::Tcp));
+    assert_eq!(ai.canonname(), None);
+    let sin = *ai.sockaddr().unwrap().as_sockaddr_in().unwrap();
+    assert_eq!(sin, SockaddrIn::new(127, 0, 0, 1, 80));
+
+    let list = getaddrinfo(Some("::1"), Some("443"), Some(&hints)).unwrap();
+    let ai = list.iter().next().unwrap();
+    let sin6 = *ai.sockaddr().unwrap().as_sockaddr_in6().unwrap();
+    assert_eq!(sin6.ip(), std::net::Ipv6Addr::LOCALHOST);
+    assert_eq!(sin6.port(), 443);
+}
+
+#[test]
+fn test_addrinfo_canonname() {
+    let mut hints = AddrInfo::default();
+    hints.set_socktype(SockType::Stream);
+    hints.set_flags(AiFlags::AI_CANONNAME | AiFlags::AI_NUMERICHOST);
+
+    let list = getaddrinfo(Some("127.0.0.1"), None, Some(&hints)).unwrap();
+    let mut iter = list.iter();
+    assert_eq!(iter.next().unwrap().canonname(), Some("127.0.0.1"));
+    // Only the first entry carries the name
+    assert!(iter.all(|ai| ai.canonname().is_none()));
+}
+
+#[test]
+fn test_addrinfo_iter_into_owned() {
+    let mut hints = AddrInfo::default();
+    hints.set_flags(AiFlags::AI_CANONNAME | AiFlags::AI_NUMERICHOST);
+
+    let list = getaddrinfo(Some("127.0.0.1"), Some("80"), Some(&hints))
+        .expect("getaddrinfo failed");
+    let first = list.iter().next().unwrap();
+    assert_eq!(first.iter().count(), list.iter().count());
+    let last = list.iter().last().unwrap();
+    assert_eq!(last.iter().count(), 1);
+
+    let owned: Vec<_> = first.iter().map(AddrInfo::into_owned).collect();
+    drop(list);
+
+    // The entries outlive the list, and can be sent to another thread
+    std::thread::spawn(move || {
+        assert_eq!(owned[0].canonname(), Some("127.0.0.1"));
+        for ai in &owned {
+            let sin = *ai.sockaddr().unwrap().as_sockaddr_in().unwrap();
+            assert_eq!(sin, SockaddrIn::new(127, 0, 0, 1, 80));
+        }
+    })
+    .join()
+    .unwrap();
+}
+
+#[cfg(linux_android)]
+#[test]
+fn test_addrinfo_ipv6_scope_id() {
+    use nix::net::if_::if_nametoindex;
+
+    let mut hints = AddrInfo::default();
+    hints.set_socktype(SockType::Stream);
+    hints.set_flags(AiFlags::AI_NUMERICHOST | AiFlags::AI_NUMERICSERV);
+
+    let list = getaddrinfo(Some("fe80::1%lo"), Some("22"), Some(&hints))
+        .expect("getaddrinfo failed");
+    let ai = list.iter().next().unwrap();
+    let sin6 = *ai.sockaddr().unwrap().as_sockaddr_in6().unwrap();
+    assert_eq!(sin6.ip(), "fe80::1".parse::<std::net::Ipv6Addr>().unwrap());
+    assert_eq!(sin6.port(), 22);
+    assert_eq!(sin6.scope_id(), if_nametoindex("lo").unwrap());
+    assert_ne!(sin6.scope_id(), 0);
+}
+
+#[test]
+fn test_addrinfo_hints_builder() {
+    use nix::netdb::AddrInfoHints;
+    use nix::sys::socket::AddressFamily;
+
+    let hints = AddrInfoHints::new()
+        .family(AddressFamily::Inet6)
+        .socktype(SockType::Datagram)
+        .protocol(SockProtocol::Udp)
+        .flags(AiFlags::AI_NUMERICHOST | AiFlags::AI_NUMERICSERV);
+    let raw = hints.as_ref();
+    assert_eq!(raw.family(), Some(AddressFamily::Inet6));
+    assert_eq!(raw.socktype(), Some(SockType::Datagram));
+    assert_eq!(raw.protocol(), Some(SockProtocol::Udp));
+    assert_eq!(
+        raw.flags(),
+        AiFlags::AI_NUMERICHOST | AiFlags::AI_NUMERICSERV
+    );
+
+    let list =
+        getaddrinfo(Some("::1"), Some("53"), Some(hints.as_ref())).unwrap();
+    for ai in &list {
+        assert_eq!(ai.family(), Some(AddressFamily::Inet6));
+        assert_eq!(ai.socktype(), Some(SockType::Datagram));
+        assert_eq!(ai.protocol(), Some(SockProtocol::Udp));
+    }
+
+    // Unset fields don't restrict anything
+    let raw = AddrInfoHints::new();
+    assert_eq!(raw.as_ref().family(), None);
+    assert_eq!(raw.as_ref().socktype(), None);
+    assert_eq!(raw.as_ref().flags(), AiFlags::empty());
+}
+
+#[test]
+fn test_address_info_error_display() {
+    use nix::errno::Errno;
+
+    let err = getaddrinfo(Some("nonexistent.invalid"), None, None).unwrap_err();
+    assert!(!err.to_string().is_empty());
+    assert_ne!(err.to_string(), format!("{err:?}"));
+
+    assert_eq!(
+        AddressInfoError::EAI_SYSTEM(Errno::EINVAL).to_string(),
+        Errno::EINVAL.desc()
+    );
+    assert!(!AddressInfoError::NoAddress.to_string().is_empty());
+
+    let boxed: Box<dyn std::error::Error> =
+        Box::new(AddressInfoError::EAI_FAMILY);
+    assert_ne!(boxed.to_string(), "EAI_FAMILY");
+}
+
+#[test]
+fn test_getservbyname() {
+    use nix::netdb::getservbyname;
+
+    let _m = crate::SERVENT_MTX.lock();
+    let Some(https) = getservbyname("https", Some("tcp")).unwrap() else {
+        skip!("https is not in the services database. Skipping test.");
+    };
+    assert_eq!(https.name(), "https");
+    assert_eq!(https.port(), 443);
+    assert_eq!(https.proto(), "tcp");
+
+    assert_eq!(getservbyname("no-such-service", None), Ok(None));
+    assert_eq!(
+        getservbyname("ht\0tps", None),
+        Err(nix::errno::Errno::EINVAL)
+    );
+}
+
+#[test]
+fn test_getservbyport() {
+    use nix::netdb::{getservbyname, getservbyport};
+
+    let _m = crate::SERVENT_MTX.lock();
+    let Some(https) = getservbyport(443, Some("tcp")).unwrap() else {
+        skip!("https is not in the services database. Skipping test.");
+    };
+    assert_eq!(https.port(), 443);
+    assert_eq!(https.proto(), "tcp");
+    assert_eq!(
+        getservbyname(https.name(), Some("tcp")).unwrap(),
+        Some(https)
+    );
+}
+
+#[test]
+fn test_reverse_lookup() {
+    use nix::netdb::reverse_lookup;
+
+    let addr = SockaddrIn::new(127, 0, 0, 1, 0);
+    // Whether the loopback address has a name depends on the host
+    let host = match reverse_lookup(&addr) {
+        Err(AddressInfoError::EAI_NONAME | AddressInfoError::EAI_AGAIN) => {
+            skip!("127.0.0.1 has no host name. Skipping test.")
+        }
+        r => r.unwrap(),
+    };
+    assert!(!host.is_empty());
+    assert_ne!(host, "127.0.0.1");
+}
+
+#[test]
+fn test_service_name_dgram() {
+    use nix::netdb::service_name;
+
+    // Port 514 is "shell" over TCP, but "syslog" over UDP
+    let addr = SockaddrIn::new(127, 0, 0, 1, 514);
+    let tcp = service_name(&addr, NiFlags::empty()).unwrap();
+    if tcp == "514" {
+        skip!("port 514 is not in the services database. Skipping test.");
+    }
+    assert_eq!(tcp, "shell");
+    assert_eq!(service_name(&addr, NiFlags::NI_DGRAM).unwrap(), "syslog");
+    assert_eq!(service_name(&addr, NiFlags::NI_NUMERICSERV).unwrap(), "514");
+}
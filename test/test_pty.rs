@@ -264,7 +264,7 @@ fn test_forkpty() {
     match res {
         ForkptyResult::Child => {
             write(stdout(), string.as_bytes()).unwrap();
-            pause(); // we need the child to stay alive until the parent calls read
+            let _ = pause(); // we need the child to stay alive until the parent calls read
             unsafe {
                 _exit(0);
             }
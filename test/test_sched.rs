@@ -37,3 +37,82 @@ fn test_sched_affinity() {
     // Finally, reset the initial CPU set
     sched_setaffinity(Pid::from_raw(0), &initial_affinity).unwrap();
 }
+
+#[cfg(linux_android)]
+#[test]
+fn test_sched_getcpu() {
+    use nix::unistd::{sysconf, SysconfVar};
+
+    // CPUs are numbered from 0, and the numbering may have holes for CPUs
+    // that are offline, so compare against the number of configured CPUs.
+    let ncpus = sysconf(SysconfVar::_NPROCESSORS_CONF).unwrap().unwrap();
+    let cpu = sched_getcpu().unwrap();
+    assert!(cpu < ncpus as usize);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_getcpu() {
+    use nix::sched::getcpu;
+
+    // Pin ourselves, so that both calls are made on the same CPU
+    let initial_affinity = sched_getaffinity(Pid::from_raw(0)).unwrap();
+    let mut pinned = CpuSet::new();
+    pinned.set(sched_getcpu().unwrap()).unwrap();
+    sched_setaffinity(Pid::from_raw(0), &pinned).unwrap();
+
+    let (cpu, node) = getcpu().unwrap();
+    assert_eq!(cpu as usize, sched_getcpu().unwrap());
+    sched_setaffinity(Pid::from_raw(0), &initial_affinity).unwrap();
+
+    // Systems without NUMA support report everything as node 0
+    let sysfs = std::path::Path::new("/sys/devices/system/node");
+    if sysfs.exists() {
+        assert!(sysfs.join(format!("node{node}")).exists());
+    } else {
+        assert_eq!(node, 0);
+    }
+}
+
+#[test]
+fn test_sched_yield() {
+    nix::sched::sched_yield().unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_setns_pidfd() {
+    use nix::errno::Errno;
+    use nix::sched::{setns_pidfd, CloneFlags};
+    use nix::unistd::{getpid, pidfd_open, PidfdFlags};
+
+    let pidfd = match pidfd_open(getpid(), PidfdFlags::empty()) {
+        Err(Errno::ENOSYS) => {
+            skip!("pidfd_open is not available. Skipping test.")
+        }
+        res => res.unwrap(),
+    };
+    // The kernel requires at least one namespace type with a pidfd.  Before
+    // Linux 5.8, it rejected pidfds with EINVAL altogether.
+    assert_eq!(
+        setns_pidfd(&pidfd, CloneFlags::empty()).unwrap_err(),
+        Errno::EINVAL
+    );
+
+    require_capability!("test_setns_pidfd", CAP_SYS_ADMIN);
+    // Re-enter our own namespaces, which is a no-op.  Do it on a separate
+    // thread, since setns changes the namespaces of the calling thread.
+    std::thread::spawn(move || {
+        let nstypes = CloneFlags::CLONE_NEWUTS
+            | CloneFlags::CLONE_NEWIPC
+            | CloneFlags::CLONE_NEWNET;
+        match setns_pidfd(&pidfd, nstypes) {
+            Err(Errno::EINVAL) => {
+                skip!("setns does not support pidfds. Skipping test.")
+            }
+            res => res.unwrap(),
+        }
+    })
+    .join()
+    .unwrap();
+}
@@ -36,3 +36,151 @@ fn test_openlog_with_ident() {
         .unwrap();
     syslog(Severity::LOG_EMERG, "Hello, ident!").unwrap();
 }
+
+#[test]
+fn test_logmask_allows() {
+    use nix::syslog::LogMask;
+
+    let mask = LogMask::up_to(Severity::LOG_WARNING);
+    assert!(mask.allows(Severity::LOG_EMERG));
+    assert!(mask.allows(Severity::LOG_ERR));
+    assert!(mask.allows(Severity::LOG_WARNING));
+    assert!(!mask.allows(Severity::LOG_NOTICE));
+    assert!(!mask.allows(Severity::LOG_DEBUG));
+
+    let mask = LogMask::of_priority(Severity::LOG_INFO)
+        | LogMask::of_priority(Severity::LOG_CRIT);
+    assert!(mask.allows(Severity::LOG_INFO));
+    assert!(mask.allows(Severity::LOG_CRIT));
+    assert!(!mask.allows(Severity::LOG_ERR));
+    assert!(!mask.allows(Severity::LOG_DEBUG));
+
+    let mask = !LogMask::of_priority(Severity::LOG_DEBUG);
+    assert!(mask.allows(Severity::LOG_INFO));
+    assert!(!mask.allows(Severity::LOG_DEBUG));
+}
+
+#[test]
+fn test_syslog_fmt() {
+    use nix::errno::Errno;
+    use nix::syslog::syslog_fmt;
+
+    let name = "syslog_fmt";
+    nix::syslog!(Severity::LOG_NOTICE, "Hello, {}!", name).unwrap();
+    // Longer than the stack buffer
+    let long = "x".repeat(1000);
+    nix::syslog!(Severity::LOG_DEBUG, "{long}").unwrap();
+    syslog_fmt(Severity::LOG_DEBUG, format_args!("{}", "y".repeat(256)))
+        .unwrap();
+
+    assert_eq!(
+        nix::syslog!(Severity::LOG_NOTICE, "embedded {}", '\0'),
+        Err(Errno::EINVAL)
+    );
+}
+
+#[test]
+fn test_setlogmask_round_trip() {
+    use nix::syslog::{setlogmask, LogMask};
+
+    let mask = LogMask::up_to(Severity::LOG_WARNING);
+    let prev = setlogmask(Some(mask));
+    // Debug messages are dropped by libc now
+    syslog(Severity::LOG_DEBUG, "test_setlogmask_round_trip").unwrap();
+    assert_eq!(setlogmask(Some(LogMask::just(Severity::LOG_ERR))), mask);
+    assert_eq!(setlogmask(None), LogMask::just(Severity::LOG_ERR));
+
+    assert_eq!(setlogmask(Some(prev)), LogMask::just(Severity::LOG_ERR));
+    assert_eq!(setlogmask(None), prev);
+}
+
+#[test]
+fn test_facility_values() {
+    #[cfg(any(linux_android, bsd, target_os = "hurd", target_os = "aix"))]
+    assert_eq!(Facility::LOG_AUTHPRIV as i32, libc::LOG_AUTHPRIV);
+    #[cfg(any(linux_android, bsd, target_os = "hurd"))]
+    assert_eq!(Facility::LOG_FTP as i32, libc::LOG_FTP);
+    #[cfg(any(
+        linux_android,
+        bsd,
+        solarish,
+        target_os = "hurd",
+        target_os = "aix"
+    ))]
+    assert_eq!(Facility::LOG_CRON as i32, libc::LOG_CRON);
+    #[cfg(freebsdlike)]
+    {
+        assert_eq!(Facility::LOG_NTP as i32, libc::LOG_NTP);
+        assert_eq!(Facility::LOG_SECURITY as i32, libc::LOG_SECURITY);
+        assert_eq!(Facility::LOG_CONSOLE as i32, libc::LOG_CONSOLE);
+    }
+}
+
+#[test]
+fn test_openlog_scoped() {
+    use nix::errno::Errno;
+    use nix::syslog::openlog_scoped;
+
+    {
+        // The guard owns its copy of the ident, so a temporary is fine
+        let ident = String::from("test_openlog_scoped");
+        let _guard = openlog_scoped(
+            Some(ident.as_str()),
+            LogFlags::LOG_PID,
+            Facility::LOG_USER,
+        )
+        .unwrap();
+        drop(ident);
+        syslog(Severity::LOG_DEBUG, "scoped").unwrap();
+    }
+    syslog(Severity::LOG_DEBUG, "unscoped").unwrap();
+
+    assert_eq!(
+        openlog_scoped(Some("nul\0"), LogFlags::empty(), Facility::LOG_USER)
+            .unwrap_err(),
+        Errno::EINVAL
+    );
+}
+
+#[test]
+fn test_openlog_owned() {
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::syslog::openlog_owned;
+    use nix::unistd::{dup2_stderr, fork, pipe, ForkResult};
+    use std::ffi::CString;
+    use std::io::Read;
+
+    let _m = crate::FORK_MTX.lock();
+
+    let (r, w) = pipe().unwrap();
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        ForkResult::Child => {
+            drop(r);
+            dup2_stderr(w).unwrap();
+            let ident = CString::new("test_openlog_owned").unwrap();
+            let ident_ptr = ident.as_ptr();
+            openlog_owned(
+                Some(ident),
+                LogFlags::LOG_PERROR,
+                Facility::LOG_USER,
+            );
+            // An allocation of the same size would reuse the ident's memory
+            // if it had been freed, and libc would then log the clobbered
+            // string.
+            let clobber = CString::new("xxxxxxxxxxxxxxxxxx").unwrap();
+            if clobber.as_ptr() == ident_ptr {
+                unsafe { libc::_exit(1) }
+            }
+            syslog(Severity::LOG_DEBUG, "owned").unwrap();
+            unsafe { libc::_exit(0) }
+        }
+        ForkResult::Parent { child } => {
+            drop(w);
+            let mut output = String::new();
+            std::fs::File::from(r).read_to_string(&mut output).unwrap();
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+            assert!(output.starts_with("test_openlog_owned"), "{output:?}");
+            assert!(output.contains("owned"));
+        }
+    }
+}
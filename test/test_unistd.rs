@@ -240,6 +240,84 @@ fn test_getpid() {
     assert!(ppid > 0);
 }
 
+#[test]
+fn test_pid_exists() {
+    assert!(Pid::this().exists().unwrap());
+    assert!(Pid::parent().exists().unwrap());
+    // Larger than any pid_max that an OS allows
+    assert!(!Pid::from_raw(libc::pid_t::MAX).exists().unwrap());
+    assert_eq!(Pid::from_raw(0).exists(), Err(Errno::EINVAL));
+}
+
+#[test]
+fn test_setpgid_self() {
+    let _m = crate::FORK_MTX.lock();
+
+    // Safe: Child only calls `setpgid`, `getpgid`, `getpgrp`, `getpid` and
+    // `_exit`, which are all signal-safe
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let ok = setpgid_self().is_ok()
+                && getpgid(None) == Ok(getpid())
+                && getpgrp() == getpid();
+            unsafe { _exit(if ok { 0 } else { 1 }) }
+        }
+        Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "freebsd", target_os = "linux"))]
+fn test_close_range() {
+    use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+
+    let file = tempfile::tempfile().unwrap();
+    // Place the fds above any that the test harness is likely to use
+    let fds: Vec<OwnedFd> = (0..3)
+        .map(|_| {
+            let fd =
+                unsafe { libc::fcntl(file.as_raw_fd(), libc::F_DUPFD, 1000) };
+            assert!(fd >= 0);
+            unsafe { OwnedFd::from_raw_fd(fd) }
+        })
+        .collect();
+    let raw: Vec<_> = fds.iter().map(AsRawFd::as_raw_fd).collect();
+    assert!(raw[0] < raw[1] && raw[1] < raw[2]);
+
+    let _m = crate::FORK_MTX.lock();
+    // Closing fds would invalidate fds owned by other tests' threads, so do
+    // it in a child.
+    // Safe: Child only calls `close_range`, `fcntl` and `_exit`, which are
+    // signal-safe
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let ok = unsafe {
+                close_range(raw[0], raw[1], CloseRangeFlags::empty()).is_ok()
+                    && libc::fcntl(raw[0], libc::F_GETFD) == -1
+                    && libc::fcntl(raw[1], libc::F_GETFD) == -1
+                    && libc::fcntl(raw[2], libc::F_GETFD) == 0
+                    && close_range(
+                        raw[2],
+                        raw[2],
+                        CloseRangeFlags::CLOSE_RANGE_CLOEXEC,
+                    )
+                    .is_ok()
+                    && libc::fcntl(raw[2], libc::F_GETFD) == libc::FD_CLOEXEC
+            };
+            unsafe { _exit(if ok { 0 } else { 1 }) }
+        }
+        Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+        }
+    }
+    assert_eq!(
+        unsafe { close_range(raw[1], raw[0], CloseRangeFlags::empty()) },
+        Err(Errno::EINVAL)
+    );
+}
+
 #[test]
 #[cfg(not(target_os = "redox"))]
 fn test_getsid() {
@@ -249,6 +327,29 @@ fn test_getsid() {
     assert_eq!(none_sid, pid_sid);
 }
 
+#[test]
+#[cfg(not(any(
+    target_os = "redox",
+    target_os = "fuchsia",
+    solarish,
+    target_os = "haiku"
+)))]
+fn test_getdtablesize() {
+    use nix::sys::resource::{getrlimit, Resource};
+
+    let (soft, _) = getrlimit(Resource::RLIMIT_NOFILE).unwrap();
+    let size = getdtablesize().unwrap();
+    assert_eq!(size, usize::try_from(soft).unwrap_or(usize::MAX));
+    assert!(size > 2);
+}
+
+#[test]
+fn test_gethostname() {
+    let hostname = gethostname().unwrap();
+    assert!(!hostname.is_empty());
+    assert_eq!(hostname, nix::sys::utsname::uname().unwrap().nodename());
+}
+
 #[cfg(linux_android)]
 mod linux_android {
     use nix::unistd::gettid;
@@ -258,6 +359,39 @@ mod linux_android {
         let tid: ::libc::pid_t = gettid().into();
         assert!(tid > 0);
     }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pidfd_getfd() {
+        use nix::errno::Errno;
+        use nix::unistd::{
+            getpid, pidfd_getfd, pidfd_open, pipe, read, write, PidfdFlags,
+        };
+        use std::os::fd::AsRawFd;
+
+        let pidfd = match pidfd_open(getpid(), PidfdFlags::empty()) {
+            Err(Errno::ENOSYS) => {
+                skip!("pidfd_open is not available. Skipping test.")
+            }
+            res => res.unwrap(),
+        };
+        let (r, w) = pipe().unwrap();
+        let stolen = match pidfd_getfd(&pidfd, w.as_raw_fd(), 0) {
+            Err(Errno::ENOSYS) => {
+                skip!("pidfd_getfd is not available. Skipping test.")
+            }
+            res => res.unwrap(),
+        };
+        assert_ne!(stolen.as_raw_fd(), w.as_raw_fd());
+        drop(w);
+
+        write(&stolen, b"hi").unwrap();
+        let mut buf = [0u8; 2];
+        assert_eq!(read(&r, &mut buf).unwrap(), 2);
+        assert_eq!(&buf, b"hi");
+
+        assert_eq!(pidfd_getfd(&pidfd, -1, 0).unwrap_err(), Errno::EBADF);
+    }
 }
 
 #[test]
@@ -490,6 +624,32 @@ fn test_fchdir() {
     assert_eq!(getcwd().unwrap(), tmpdir_path);
 }
 
+#[test]
+#[cfg(not(target_os = "fuchsia"))]
+fn test_chdir_guard() {
+    let _dr = crate::DirRestore::new();
+
+    let original = getcwd().unwrap();
+    let tmpdir = tempdir().unwrap();
+    let tmpdir_path = tmpdir.path().canonicalize().unwrap();
+
+    fn inner(dir: &Path) -> nix::Result<()> {
+        let _guard = ChdirGuard::new(dir)?;
+        assert_eq!(getcwd().unwrap(), dir);
+        // Leave early through an error
+        Err(Errno::EIO)
+    }
+    assert_eq!(inner(&tmpdir_path), Err(Errno::EIO));
+    assert_eq!(getcwd().unwrap(), original);
+
+    // A failed change leaves the working directory alone
+    assert_eq!(
+        ChdirGuard::new(&tmpdir_path.join("missing")).unwrap_err(),
+        Errno::ENOENT
+    );
+    assert_eq!(getcwd().unwrap(), original);
+}
+
 #[test]
 fn test_getcwd() {
     // chdir changes the process's cwd
@@ -725,6 +885,17 @@ fn test_getresgid() {
     assert_ne!(resgids.saved.as_raw(), libc::gid_t::MAX);
 }
 
+#[cfg(any(linux_android, freebsdlike, target_os = "openbsd"))]
+#[test]
+fn test_credentials_current() {
+    let creds = Credentials::current().unwrap();
+    assert_eq!(creds.uid.real, getuid());
+    assert_eq!(creds.uid.effective, geteuid());
+    assert_eq!(creds.gid.real, getgid());
+    assert_eq!(creds.gid.effective, getegid());
+    assert_eq!(creds.groups, getgroups().unwrap());
+}
+
 // Test that we can create a pair of pipes.  No need to verify that they pass
 // data; that's the domain of the OS, not nix.
 #[test]
@@ -741,6 +912,56 @@ fn test_pipe() {
     assert_eq!(m1, SFlag::S_IFIFO);
 }
 
+#[test]
+fn test_write_all() {
+    let (rd, wr) = pipe().unwrap();
+    // Much larger than a pipe's buffer, so the writes have to be partial
+    let data: Vec<u8> = (0..(1 << 20)).map(|i| i as u8).collect();
+    let expected = data.clone();
+
+    let writer = std::thread::spawn(move || write_all(wr, &data));
+    let mut received = Vec::new();
+    let mut buf = [0u8; 65536];
+    loop {
+        match read(&rd, &mut buf).unwrap() {
+            0 => break,
+            n => received.extend_from_slice(&buf[..n]),
+        }
+    }
+    writer.join().unwrap().unwrap();
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn test_read_exact() {
+    let (rd, wr) = pipe().unwrap();
+    write(&wr, b"hello").unwrap();
+    write(&wr, b" world").unwrap();
+    let mut buf = [0u8; 11];
+    nix::unistd::read_exact(&rd, &mut buf).unwrap();
+    assert_eq!(&buf, b"hello world");
+
+    write(&wr, b"short").unwrap();
+    drop(wr);
+    let mut buf = [0u8; 6];
+    assert_eq!(nix::unistd::read_exact(&rd, &mut buf), Err(Errno::EIO));
+}
+
+#[test]
+fn test_read_to_end() {
+    let (rd, wr) = pipe().unwrap();
+    let data: Vec<u8> = (0..100_000).map(|i| i as u8).collect();
+    let expected = data.clone();
+    let writer = std::thread::spawn(move || write_all(wr, &data));
+
+    let mut buf = b"prefix".to_vec();
+    let n = read_to_end(&rd, &mut buf).unwrap();
+    writer.join().unwrap().unwrap();
+    assert_eq!(n, expected.len());
+    assert_eq!(&buf[..6], b"prefix");
+    assert_eq!(&buf[6..], &expected[..]);
+}
+
 // pipe2(2) is the same as pipe(2), except it allows setting some flags.  Check
 // that we can set a flag.
 #[cfg(any(
@@ -865,6 +1086,75 @@ fn test_canceling_alarm() {
     assert_eq!(alarm::cancel(), Some(60));
 }
 
+#[test]
+#[cfg(not(target_os = "redox"))]
+fn test_alarm_set_duration() {
+    use std::time::Duration;
+
+    let _m = crate::SIGNAL_MTX.lock();
+
+    assert_eq!(alarm::set_duration(Duration::from_millis(59_500)), None);
+    assert_eq!(
+        alarm::set_duration(Duration::from_secs(60)),
+        Some(Duration::from_secs(60))
+    );
+    assert_eq!(alarm::cancel(), Some(60));
+}
+
+#[test]
+fn test_redirect_stdio() {
+    use std::os::unix::io::AsFd;
+
+    let _m = crate::FORK_MTX.lock();
+    let (reader, writer) = pipe().unwrap();
+
+    // Safe: Child only calls `redirect_stdio`, `fcntl`, `write` and `_exit`
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let ok = redirect_stdio(None, Some(writer.as_fd()), None).is_ok()
+                && unsafe { libc::fcntl(libc::STDOUT_FILENO, libc::F_GETFD) }
+                    & libc::FD_CLOEXEC
+                    == 0
+                && write(std::io::stdout(), b"redirected").is_ok();
+            unsafe { _exit(if ok { 0 } else { 1 }) }
+        }
+        Parent { child } => {
+            drop(writer);
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)));
+            let mut buf = [0u8; 10];
+            crate::read_exact(&reader, &mut buf);
+            assert_eq!(&buf, b"redirected");
+        }
+    }
+}
+
+#[test]
+#[cfg(not(target_os = "redox"))]
+fn test_pause_eintr() {
+    extern "C" fn handler(_: libc::c_int) {}
+
+    let _m = crate::FORK_MTX.lock();
+
+    // The alarm is delivered to the whole process, so wait for it in a
+    // single-threaded child where it cannot land on another test's thread.
+    match unsafe { fork() }.expect("Error: Fork Failed") {
+        Child => {
+            let sa = SigAction::new(
+                SigHandler::Handler(handler),
+                SaFlags::empty(),
+                SigSet::empty(),
+            );
+            let ok = unsafe { sigaction(Signal::SIGALRM, &sa) }.is_ok()
+                && alarm::set(1).is_none()
+                && pause() == Err(Errno::EINTR);
+            unsafe { _exit(if ok { 0 } else { 1 }) }
+        }
+        Parent { child } => {
+            assert_eq!(waitpid(child, None), Ok(WaitStatus::Exited(child, 0)))
+        }
+    }
+}
+
 #[test]
 #[cfg(not(any(target_os = "redox", target_os = "haiku")))]
 fn test_symlinkat() {
@@ -1272,6 +1562,28 @@ fn test_setfsuid() {
     fs::File::open(temp_path_2).unwrap();
 }
 
+/// Tests setting and restoring the filesystem GID with `setfsgid`.
+#[cfg(linux_android)]
+#[test]
+fn test_setfsgid() {
+    use std::thread;
+    require_capability!("test_setfsgid", CAP_SETGID);
+
+    let nobody = User::from_name("nobody").unwrap().unwrap();
+    let query = Gid::from_raw(u32::MAX);
+
+    // Use a new thread, since the filesystem GID is per-thread
+    thread::spawn(move || {
+        let orig = setfsgid(nobody.gid);
+        assert_eq!(setfsgid(query), nobody.gid);
+
+        assert_eq!(setfsgid(orig), nobody.gid);
+        assert_eq!(setfsgid(query), orig);
+    })
+    .join()
+    .unwrap();
+}
+
 #[test]
 #[cfg(not(any(
     target_os = "redox",
@@ -1296,17 +1608,77 @@ fn test_ttyname() {
         .open(Path::new(&sname))
         .expect("open failed");
 
+    assert!(isatty(&fds).unwrap());
     let name = ttyname(fds).expect("ttyname failed");
     assert!(name.starts_with("/dev"));
+    #[cfg(linux_android)]
+    {
+        assert!(name.starts_with("/dev/pts/"));
+        assert_eq!(name, Path::new(&sname));
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_vhangup_eperm() {
+    use caps::{CapSet, Capability};
+
+    let _m = crate::FORK_MTX.lock();
+
+    // Capabilities are per thread and inherited across fork, so drop them in a
+    // thread of our own and fork from there.
+    std::thread::spawn(|| {
+        caps::drop(None, CapSet::Effective, Capability::CAP_SYS_TTY_CONFIG)
+            .unwrap();
+
+        // Safe: the child only makes system calls before calling _exit
+        match unsafe { fork() }.expect("Error: Fork Failed") {
+            Child => {
+                // Leave our controlling terminal behind, so that nothing can
+                // be hung up even if the capability was somehow retained.
+                let code = if setsid().is_err() {
+                    1
+                } else {
+                    match vhangup() {
+                        Err(Errno::EPERM) => 0,
+                        _ => 2,
+                    }
+                };
+                unsafe { _exit(code) };
+            }
+            Parent { child } => {
+                assert_eq!(
+                    waitpid(child, None),
+                    Ok(WaitStatus::Exited(child, 0))
+                );
+            }
+        }
+    })
+    .join()
+    .unwrap();
 }
 
 #[test]
 #[cfg(not(any(target_os = "redox", target_os = "fuchsia")))]
 fn test_ttyname_not_pty() {
     let fd = File::open("/dev/zero").unwrap();
+    assert!(!isatty(&fd).unwrap());
     assert_eq!(ttyname(fd), Err(Errno::ENOTTY));
 }
 
+#[test]
+#[cfg(any(
+    all(target_os = "linux", any(target_env = "gnu", target_env = "musl")),
+    target_os = "aix",
+    target_os = "haiku",
+    target_os = "hurd",
+))]
+fn test_ctermid() {
+    let path = ctermid().unwrap();
+    assert!(!path.as_os_str().is_empty());
+    assert!(path.starts_with("/dev"));
+}
+
 #[test]
 #[cfg(bsd)]
 fn test_getpeereid() {